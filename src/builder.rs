@@ -0,0 +1,311 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_LANGUAGE, USER_AGENT};
+
+use crate::error::ConfigError;
+use crate::middleware::{Middleware, Middlewares};
+use crate::{AuthStore, PocketBase, RetryPolicy};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A builder to configure and create a [`PocketBase`] client.
+///
+/// Created with [`PocketBase::builder`]. Unlike [`PocketBase::new`], invalid settings are
+/// returned as a [`ConfigError`] by [`PocketBaseBuilder::build`] instead of panicking.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+///
+/// use pocketbase_rs::PocketBase;
+///
+/// let pb = PocketBase::builder("https://pocketbase.example.com")
+///     .timeout(Duration::from_secs(60))
+///     .user_agent("my-app/1.0")
+///     .language("fr-FR")
+///     .header("X-Tenant", "acme")
+///     .build()?;
+/// ```
+#[derive(Debug)]
+pub struct PocketBaseBuilder {
+    base_url: String,
+    timeout: Duration,
+    connect_timeout: Duration,
+    user_agent: Option<String>,
+    language: Option<String>,
+    headers: Vec<(String, String)>,
+    proxy: Option<String>,
+    auth_store: Option<AuthStore>,
+    retry_policy: Option<RetryPolicy>,
+    middlewares: Middlewares,
+}
+
+impl PocketBase {
+    /// Creates a [`PocketBaseBuilder`] to configure a client for the given base URL.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let pb = PocketBase::builder("http://localhost:8090")
+    ///     .timeout(Duration::from_secs(5))
+    ///     .build()?;
+    /// ```
+    pub fn builder(base_url: impl Into<String>) -> PocketBaseBuilder {
+        PocketBaseBuilder {
+            base_url: base_url.into(),
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            user_agent: None,
+            language: None,
+            headers: Vec::new(),
+            proxy: None,
+            auth_store: None,
+            retry_policy: None,
+            middlewares: Middlewares::default(),
+        }
+    }
+}
+
+impl PocketBaseBuilder {
+    /// Sets the timeout of the whole request, from connection to the end of the response body
+    /// *(default to 30 seconds)*.
+    ///
+    /// The realtime connection is not affected, as it stays open.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout of the connection to the server *(default to 10 seconds)*.
+    #[must_use]
+    pub const fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    #[must_use]
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets the `Accept-Language` header sent with every request *(example: `"fr-FR"`)*,
+    /// used by `PocketBase` to localize its messages and emails.
+    #[must_use]
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Adds a header sent with every request.
+    ///
+    /// Can be called multiple times. A header added twice is sent with both values.
+    #[must_use]
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sends every request, including the realtime connection, through the given proxy
+    /// *(example: `"http://proxy.local:3128"` or `"socks5://127.0.0.1:1080"`)*.
+    #[must_use]
+    pub fn proxy(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy = Some(proxy_url.into());
+        self
+    }
+
+    /// Starts the client with an existing session, for example one saved by a previous run.
+    ///
+    /// The token can be renewed like one obtained by an authentication method.
+    #[must_use]
+    pub fn auth_store(mut self, auth_store: AuthStore) -> Self {
+        self.auth_store = Some(auth_store);
+        self
+    }
+
+    /// Retries the requests that failed for a transient reason, according to the given policy.
+    ///
+    /// Requests are not retried by default.
+    #[must_use]
+    pub const fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Adds a [`Middleware`] called around every request.
+    ///
+    /// Can be called multiple times. Middlewares are called in the order they were added.
+    #[must_use]
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// Creates the [`PocketBase`] client.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if:
+    /// - The base URL can't be parsed (`ConfigError::InvalidUrl`).
+    /// - The base URL doesn't use `http` or `https` (`ConfigError::UnsupportedScheme`).
+    /// - The base URL has a query string or a fragment (`ConfigError::UnexpectedQuery`).
+    /// - A header name or value is invalid, including the user agent and the language (`ConfigError::InvalidHeader`).
+    /// - The proxy URL is invalid (`ConfigError::InvalidProxy`).
+    /// - The HTTP client couldn't be initialized (`ConfigError::HttpClient`).
+    pub fn build(self) -> Result<PocketBase, ConfigError> {
+        let base_url = parse_base_url(&self.base_url)?;
+
+        let mut headers = HeaderMap::new();
+
+        if let Some(user_agent) = &self.user_agent {
+            headers.insert(USER_AGENT, header_value(USER_AGENT.as_str(), user_agent)?);
+        }
+
+        if let Some(language) = &self.language {
+            headers.insert(
+                ACCEPT_LANGUAGE,
+                header_value(ACCEPT_LANGUAGE.as_str(), language)?,
+            );
+        }
+
+        for (name, value) in &self.headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| ConfigError::InvalidHeader(name.clone()))?;
+
+            headers.append(header_name, header_value(name, value)?);
+        }
+
+        let mut client_builder = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout)
+            .default_headers(headers);
+
+        if let Some(proxy_url) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy_url).map_err(ConfigError::InvalidProxy)?;
+            client_builder = client_builder.proxy(proxy);
+        }
+
+        let reqwest_client = client_builder.build().map_err(ConfigError::HttpClient)?;
+
+        let pocketbase = PocketBase {
+            base_url,
+            auth: std::sync::Arc::default(),
+            auto_refresh: None,
+            retry_policy: self.retry_policy,
+            middlewares: self.middlewares,
+            reqwest_client,
+            realtime: std::sync::Arc::default(),
+        };
+
+        if let Some(auth_store) = self.auth_store {
+            pocketbase.update_auth_store(auth_store);
+        }
+
+        Ok(pocketbase)
+    }
+}
+
+/// Validates the base URL of a `PocketBase` instance, and removes its trailing slashes.
+pub fn parse_base_url(base_url: &str) -> Result<String, ConfigError> {
+    let url = url::Url::parse(base_url)?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(ConfigError::UnsupportedScheme(url.scheme().to_string()));
+    }
+
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(ConfigError::UnexpectedQuery(base_url.to_string()));
+    }
+
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+fn header_value(name: &str, value: &str) -> Result<HeaderValue, ConfigError> {
+    HeaderValue::from_str(value).map_err(|_| ConfigError::InvalidHeader(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_trailing_slashes() {
+        assert_eq!(
+            parse_base_url("http://localhost:8090/").unwrap(),
+            "http://localhost:8090"
+        );
+        assert_eq!(
+            parse_base_url("https://example.com/pb//").unwrap(),
+            "https://example.com/pb"
+        );
+    }
+
+    #[test]
+    fn rejects_other_schemes() {
+        assert!(matches!(
+            parse_base_url("ftp://example.com"),
+            Err(ConfigError::UnsupportedScheme(scheme)) if scheme == "ftp"
+        ));
+        assert!(matches!(
+            parse_base_url("localhost:8090"),
+            Err(ConfigError::UnsupportedScheme(_))
+        ));
+        assert!(matches!(
+            parse_base_url("not a url"),
+            Err(ConfigError::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_queries_and_fragments() {
+        assert!(matches!(
+            parse_base_url("http://localhost:8090/?a=b"),
+            Err(ConfigError::UnexpectedQuery(_))
+        ));
+        assert!(matches!(
+            parse_base_url("http://localhost:8090/#top"),
+            Err(ConfigError::UnexpectedQuery(_))
+        ));
+    }
+
+    #[test]
+    fn builds_a_client() {
+        let pb = PocketBase::builder("http://localhost:8090/")
+            .user_agent("my-app/1.0")
+            .header("X-Tenant", "acme")
+            .build()
+            .unwrap();
+
+        assert_eq!(pb.base_url(), "http://localhost:8090");
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        let result = PocketBase::builder("http://localhost:8090")
+            .header("X-Tenant", "line\nbreak")
+            .build();
+        assert!(matches!(result, Err(ConfigError::InvalidHeader(name)) if name == "X-Tenant"));
+
+        let result = PocketBase::builder("http://localhost:8090")
+            .header("Invalid Name", "value")
+            .build();
+        assert!(matches!(result, Err(ConfigError::InvalidHeader(name)) if name == "Invalid Name"));
+
+        let result = PocketBase::builder("http://localhost:8090")
+            .user_agent("my-app\r\n")
+            .build();
+        assert!(matches!(result, Err(ConfigError::InvalidHeader(name)) if name == "user-agent"));
+    }
+
+    #[test]
+    fn rejects_invalid_urls_when_building() {
+        assert!(matches!(
+            PocketBase::builder("http://localhost:8090?a=b").build(),
+            Err(ConfigError::UnexpectedQuery(_))
+        ));
+    }
+}
@@ -2,13 +2,28 @@
 
 use core::fmt;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::records::auth::MfaRequiredResponse;
+use crate::ErrorResponse;
+
+pub use crate::realtime::RealtimeError;
+pub use crate::records::auth::auth_store_backend::AuthStoreBackendError;
+pub use crate::records::auth::auth_with_otp::AuthWithOtpError;
 pub use crate::records::auth::auth_with_password::AuthenticationError;
+pub use crate::records::auth::confirm_email_change::ConfirmEmailChangeError;
+pub use crate::records::auth::confirm_password_reset::ConfirmPasswordResetError;
+pub use crate::records::auth::confirm_verification::ConfirmVerificationError;
 pub use crate::records::auth::impersonate::ImpersonateError;
+pub use crate::records::auth::request_email_change::RequestEmailChangeError;
+pub use crate::records::auth::request_otp::RequestOtpError;
+pub use crate::records::auth::request_password_reset::RequestPasswordResetError;
 pub use crate::records::crud::create::CreateError;
+pub use crate::records::crud::delete::DeleteError;
 pub use crate::records::crud::update::UpdateError;
 
 /// This error represents the error returned by the `PocketBase`
@@ -27,7 +42,7 @@ pub struct BadRequestResponse {
 ///
 /// This struct holds detailed information about a single validation error,
 /// including the field name, error code, and a user-friendly message.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct BadRequestError {
     /// Name of the field.
     pub name: String,
@@ -52,52 +67,635 @@ pub struct BadRequestField {
     pub message: String,
 }
 
+/// Represents the errors of an invalid collection name, given to [`crate::PocketBase::collection`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CollectionNameError {
+    /// The collection name is empty.
+    #[error("Collection name cannot be empty.")]
+    Empty,
+    /// The collection name contains other characters than alphanumeric characters and underscores.
+    #[error("Collection name `{0}` contains invalid characters. Only alphanumeric characters and underscores are allowed.")]
+    InvalidCharacters(String),
+}
+
+/// Represents the errors of a [`crate::Filter`] that can't be rendered to the `PocketBase` filter syntax.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum FilterError {
+    /// The text ends with a backslash, which would escape its closing quote.
+    #[error("The filter value `{0}` ends with a backslash, and can't be quoted.")]
+    UnquotableText(String),
+    /// The number is `NaN` or infinite, which the filter syntax can't represent.
+    #[error("The filter value `{0}` is not a finite number.")]
+    NonFiniteNumber(f64),
+}
+
+/// Represents the errors of an invalid client configuration, given to [`crate::PocketBaseBuilder`].
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    /// The base URL couldn't be parsed.
+    #[error("Invalid base URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    /// The base URL uses another scheme than `http` or `https`.
+    #[error("Unsupported base URL scheme `{0}`: must be http or https.")]
+    UnsupportedScheme(String),
+    /// The base URL has a query string or a fragment, which the endpoints can't be appended to.
+    #[error("The base URL `{0}` can't have a query string or a fragment.")]
+    UnexpectedQuery(String),
+    /// The name or the value of the given header is invalid.
+    #[error("Invalid name or value for the header `{0}`.")]
+    InvalidHeader(String),
+    /// The proxy URL is invalid.
+    #[error("Invalid proxy: {0}")]
+    InvalidProxy(#[source] reqwest::Error),
+    /// The HTTP client couldn't be initialized, for example because the TLS backend failed to load.
+    #[error("The HTTP client couldn't be created: {0}")]
+    HttpClient(#[source] reqwest::Error),
+}
+
 /// Represents errors when interacting with the `PocketBase` API.
 ///
 /// This enum provides a set of error types that may occur during
 /// API requests, each indicating a specific issue encountered.
+///
+/// Each variant holds the [`Error`] it was created from, with the HTTP status and everything
+/// returned by `PocketBase`.
 #[derive(Error, Debug)]
 pub enum RequestError {
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
     ///
     /// Your request may be missing fields or its content doesn't match what `PocketBase` expects to receive.
-    #[error("Bad Request: Something went wrong while processing your request. {0}")]
-    BadRequest(String),
+    #[error("Bad Request: Something went wrong while processing your request. {}", .0.message())]
+    BadRequest(Error),
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") HTTP error response.
     ///
     /// The request may require an Authorization Token.
     #[error("Unauthorized: The request may require an Authorization Token.")]
-    Unauthorized,
+    Unauthorized(Error),
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
     ///
     /// The authenticated user may not have permissions for this interaction.
     #[error("Forbidden: The authenticated user may not have permissions for this interaction.")]
-    Forbidden,
+    Forbidden(Error),
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
     #[error("Not Found: The requested resource could not be found.")]
-    NotFound,
+    NotFound(Error),
     /// The response could not be parsed into the expected data structure.
-    #[error("Parse Error: Could not parse response into the expected data structure. It usually means that there is a missmatch between the provided Generic Type Parameter and your Collection definition. - {0}")]
-    ParseError(String),
+    #[error("Parse Error: Could not parse response into the expected data structure. It usually means that there is a missmatch between the provided Generic Type Parameter and your Collection definition. - {}", .0.message())]
+    ParseError(Error),
     /// The `PocketBase` API interaction timed out. It may be offline.
     #[error(
         "Unreachable: The PocketBase API interaction timed out, or the service may be offline."
     )]
-    Unreachable,
+    Unreachable(Error),
     /// Too many requests were sent to the API.
     ///
     /// The server is rate limiting requests. Wait before retrying.
     #[error(
         "Too Many Requests: The server is rate limiting requests. Please wait before retrying."
     )]
-    TooManyRequests,
+    TooManyRequests(Error),
     /// Unhandled error.
     ///
     /// Usually emitted when something unexpected happened, and isn't handled correctly by this crate.
     #[error("Unhandled Error: An unexpected error occurred.")]
-    Unhandled,
+    Unhandled(Error),
+}
+
+/// Represents the category of an [`Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The `PocketBase` API returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
+    ///
+    /// The fields that were not validated are available with [`Error::data`].
+    BadRequest,
+    /// The `PocketBase` API returned a [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") HTTP error response.
+    Unauthorized,
+    /// The `PocketBase` API returned a [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") HTTP error response
+    /// asking for a second authentication factor, whose session id is available with [`Error::mfa_id`].
+    MfaRequired,
+    /// The `PocketBase` API returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
+    Forbidden,
+    /// The `PocketBase` API returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
+    NotFound,
+    /// The `PocketBase` API returned a [429 Too Many Requests]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/429") HTTP error response.
+    TooManyRequests,
+    /// The `PocketBase` API returned a 5xx HTTP error response.
+    ServerError,
+    /// The `PocketBase` API returned a status code that is not handled by this crate.
+    UnexpectedResponse,
+    /// Communication with the `PocketBase` API failed *(connection error, timeout...)*.
+    Unreachable,
+    /// The response could not be parsed into the expected data structure.
+    ParseError,
+    /// The request was not sent, as its parameters are invalid *(example: an empty collection name)*.
+    InvalidInput,
+}
+
+impl ErrorKind {
+    fn from_status(status: reqwest::StatusCode) -> Self {
+        match status {
+            reqwest::StatusCode::BAD_REQUEST => Self::BadRequest,
+            reqwest::StatusCode::UNAUTHORIZED => Self::Unauthorized,
+            reqwest::StatusCode::FORBIDDEN => Self::Forbidden,
+            reqwest::StatusCode::NOT_FOUND => Self::NotFound,
+            reqwest::StatusCode::TOO_MANY_REQUESTS => Self::TooManyRequests,
+            status if status.is_server_error() => Self::ServerError,
+            _ => Self::UnexpectedResponse,
+        }
+    }
+}
+
+/// The error of any operation on the `PocketBase` API.
+///
+/// Unlike the errors specific to each operation *(such as [`RequestError`] or [`CreateError`])*,
+/// it keeps everything returned by `PocketBase`: the HTTP status, the `message` of the JSON error
+/// body, and its field errors. Every operation error converts into it, so `?` can be used on
+/// operations returning different errors.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use pocketbase_rs::{Error, ErrorKind, PocketBase, Record};
+///
+/// async fn publish(pb: &PocketBase, article: Article) -> Result<(), Error> {
+///     pb.collection("users")?
+///         .auth_with_password("test@domain.com", "secure-password")
+///         .await?;
+///
+///     pb.collection("articles")?
+///         .create(article)
+///         .call::<Record<()>>()
+///         .await?;
+///
+///     Ok(())
+/// }
+///
+/// match publish(&pb, article).await {
+///     Err(error) if error.kind() == ErrorKind::BadRequest => {
+///         for field in error.data() {
+///             eprintln!("{field}");
+///         }
+///     }
+///     Err(error) => eprintln!("Error: {error}"),
+///     Ok(()) => {}
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Error {
+    kind: ErrorKind,
+    status: Option<reqwest::StatusCode>,
+    message: String,
+    data: Vec<BadRequestError>,
+    mfa_id: Option<String>,
+    elapsed: Option<Duration>,
+    source: Option<Arc<reqwest::Error>>,
+}
+
+impl Error {
+    pub(crate) fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            status: None,
+            message: message.into(),
+            data: Vec::new(),
+            mfa_id: None,
+            elapsed: None,
+            source: None,
+        }
+    }
+
+    #[must_use]
+    pub(crate) const fn with_elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = Some(elapsed);
+        self
+    }
+
+    /// Creates an error from an unsuccessful response, parsing the JSON error body of `PocketBase`.
+    ///
+    /// ```json
+    /// {
+    ///     "status": 400,
+    ///     "message": "Failed to create record.",
+    ///     "data": {
+    ///         "title": {
+    ///             "code": "validation_required",
+    ///             "message": "Missing required value."
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub(crate) async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let body = response.bytes().await.unwrap_or_default();
+
+        let mut error = Self::new(ErrorKind::from_status(status), "");
+        error.status = Some(status);
+
+        // {
+        //     "mfaId": "..."
+        // }
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            if let Ok(mfa) = serde_json::from_slice::<MfaRequiredResponse>(&body) {
+                error.kind = ErrorKind::MfaRequired;
+                error.message = "Multi-factor authentication is required.".to_string();
+                error.mfa_id = Some(mfa.mfa_id);
+
+                return error;
+            }
+        }
+
+        match serde_json::from_slice::<ErrorResponse>(&body) {
+            Ok(error_response) => {
+                error.message.clone_from(&error_response.message);
+                error.data = error_response.into_errors();
+            }
+            Err(_) => {
+                error.message = status.canonical_reason().unwrap_or_default().to_string();
+            }
+        }
+
+        error
+    }
+
+    /// Returns the category of the error.
+    #[must_use]
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the HTTP status of the response, if one was received.
+    #[must_use]
+    pub const fn status(&self) -> Option<reqwest::StatusCode> {
+        self.status
+    }
+
+    /// Returns the `message` sent by `PocketBase`, or a description of the error when the
+    /// response had no JSON error body.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the field errors sent by `PocketBase`, usually with a [`ErrorKind::BadRequest`].
+    #[must_use]
+    pub fn data(&self) -> &[BadRequestError] {
+        &self.data
+    }
+
+    /// Returns the id of the multi-factor authentication session, with a [`ErrorKind::MfaRequired`].
+    #[must_use]
+    pub fn mfa_id(&self) -> Option<&str> {
+        self.mfa_id.as_deref()
+    }
+
+    /// Returns the time elapsed between sending the request and the failure, if the request was sent.
+    ///
+    /// Useful to tell a slow server from a refused connection, for example.
+    #[must_use]
+    pub const fn elapsed(&self) -> Option<Duration> {
+        self.elapsed
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.status {
+            Some(status) => write!(f, "{status}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        let mut converted = if error.is_decode() {
+            Self::new(ErrorKind::ParseError, error.to_string())
+        } else if let Some(status) = error.status() {
+            let mut converted = Self::new(ErrorKind::from_status(status), error.to_string());
+            converted.status = Some(status);
+            converted
+        } else {
+            Self::new(ErrorKind::Unreachable, error.to_string())
+        };
+
+        converted.source = Some(Arc::new(error));
+        converted
+    }
+}
+
+impl From<CollectionNameError> for Error {
+    fn from(error: CollectionNameError) -> Self {
+        Self::new(ErrorKind::InvalidInput, error.to_string())
+    }
+}
+
+impl From<FilterError> for Error {
+    fn from(error: FilterError) -> Self {
+        Self::new(ErrorKind::InvalidInput, error.to_string())
+    }
+}
+
+impl From<RequestError> for Error {
+    fn from(error: RequestError) -> Self {
+        match error {
+            RequestError::BadRequest(error)
+            | RequestError::Unauthorized(error)
+            | RequestError::Forbidden(error)
+            | RequestError::NotFound(error)
+            | RequestError::ParseError(error)
+            | RequestError::Unreachable(error)
+            | RequestError::TooManyRequests(error)
+            | RequestError::Unhandled(error) => error,
+        }
+    }
+}
+
+impl From<Error> for RequestError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            ErrorKind::BadRequest | ErrorKind::InvalidInput => Self::BadRequest(error),
+            ErrorKind::Unauthorized | ErrorKind::MfaRequired => Self::Unauthorized(error),
+            ErrorKind::Forbidden => Self::Forbidden(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::TooManyRequests => Self::TooManyRequests(error),
+            ErrorKind::Unreachable => Self::Unreachable(error),
+            ErrorKind::ParseError => Self::ParseError(error),
+            ErrorKind::ServerError | ErrorKind::UnexpectedResponse => Self::Unhandled(error),
+        }
+    }
+}
+
+impl From<AuthenticationError> for Error {
+    fn from(error: AuthenticationError) -> Self {
+        match error {
+            AuthenticationError::InvalidCredentials(error)
+            | AuthenticationError::BadRequest(error)
+            | AuthenticationError::MfaRequired { error, .. }
+            | AuthenticationError::EmptyField { error, .. }
+            | AuthenticationError::IdentityMustBeEmail(error)
+            | AuthenticationError::Forbidden(error)
+            | AuthenticationError::NotFound(error)
+            | AuthenticationError::TooManyRequests(error)
+            | AuthenticationError::HttpError(error)
+            | AuthenticationError::UnexpectedResponse(error) => error,
+            AuthenticationError::MissingCollection => {
+                Self::new(ErrorKind::InvalidInput, error.to_string())
+            }
+        }
+    }
+}
+
+impl From<AuthWithOtpError> for Error {
+    fn from(error: AuthWithOtpError) -> Self {
+        match error {
+            AuthWithOtpError::InvalidOrExpiredOtp(error)
+            | AuthWithOtpError::MfaRequired { error, .. }
+            | AuthWithOtpError::BadRequest(error)
+            | AuthWithOtpError::Forbidden(error)
+            | AuthWithOtpError::NotFound(error)
+            | AuthWithOtpError::TooManyRequests(error)
+            | AuthWithOtpError::Unreachable(error)
+            | AuthWithOtpError::ParseError(error)
+            | AuthWithOtpError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<RequestOtpError> for Error {
+    fn from(error: RequestOtpError) -> Self {
+        match error {
+            RequestOtpError::BadRequest(error)
+            | RequestOtpError::Forbidden(error)
+            | RequestOtpError::NotFound(error)
+            | RequestOtpError::TooManyRequests(error)
+            | RequestOtpError::Unreachable(error)
+            | RequestOtpError::ParseError(error)
+            | RequestOtpError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<ImpersonateError> for Error {
+    fn from(error: ImpersonateError) -> Self {
+        match error {
+            ImpersonateError::BadRequest(error)
+            | ImpersonateError::Unauthorized(error)
+            | ImpersonateError::Forbidden(error)
+            | ImpersonateError::NotFound(error)
+            | ImpersonateError::TooManyRequests(error)
+            | ImpersonateError::Unreachable(error)
+            | ImpersonateError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<ConfirmVerificationError> for Error {
+    fn from(error: ConfirmVerificationError) -> Self {
+        match error {
+            ConfirmVerificationError::BadRequest(error)
+            | ConfirmVerificationError::NotFound(error)
+            | ConfirmVerificationError::TooManyRequests(error)
+            | ConfirmVerificationError::Unreachable(error)
+            | ConfirmVerificationError::ParseError(error)
+            | ConfirmVerificationError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<RequestPasswordResetError> for Error {
+    fn from(error: RequestPasswordResetError) -> Self {
+        match error {
+            RequestPasswordResetError::BadRequest(error)
+            | RequestPasswordResetError::NotFound(error)
+            | RequestPasswordResetError::TooManyRequests(error)
+            | RequestPasswordResetError::Unreachable(error)
+            | RequestPasswordResetError::ParseError(error)
+            | RequestPasswordResetError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<ConfirmPasswordResetError> for Error {
+    fn from(error: ConfirmPasswordResetError) -> Self {
+        match error {
+            ConfirmPasswordResetError::BadRequest(error)
+            | ConfirmPasswordResetError::NotFound(error)
+            | ConfirmPasswordResetError::TooManyRequests(error)
+            | ConfirmPasswordResetError::Unreachable(error)
+            | ConfirmPasswordResetError::ParseError(error)
+            | ConfirmPasswordResetError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<RequestEmailChangeError> for Error {
+    fn from(error: RequestEmailChangeError) -> Self {
+        match error {
+            RequestEmailChangeError::BadRequest(error)
+            | RequestEmailChangeError::Unauthorized(error)
+            | RequestEmailChangeError::Forbidden(error)
+            | RequestEmailChangeError::NotFound(error)
+            | RequestEmailChangeError::TooManyRequests(error)
+            | RequestEmailChangeError::Unreachable(error)
+            | RequestEmailChangeError::ParseError(error)
+            | RequestEmailChangeError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<ConfirmEmailChangeError> for Error {
+    fn from(error: ConfirmEmailChangeError) -> Self {
+        match error {
+            ConfirmEmailChangeError::BadRequest(error)
+            | ConfirmEmailChangeError::NotFound(error)
+            | ConfirmEmailChangeError::TooManyRequests(error)
+            | ConfirmEmailChangeError::Unreachable(error)
+            | ConfirmEmailChangeError::ParseError(error)
+            | ConfirmEmailChangeError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<CreateError> for Error {
+    fn from(error: CreateError) -> Self {
+        match error {
+            CreateError::BadRequest(error)
+            | CreateError::Unauthorized(error)
+            | CreateError::Forbidden(error)
+            | CreateError::NotFound(error)
+            | CreateError::TooManyRequests(error)
+            | CreateError::Unreachable(error)
+            | CreateError::ParseError(error)
+            | CreateError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<UpdateError> for Error {
+    fn from(error: UpdateError) -> Self {
+        match error {
+            UpdateError::BadRequest(error)
+            | UpdateError::Unauthorized(error)
+            | UpdateError::Forbidden(error)
+            | UpdateError::NotFound(error)
+            | UpdateError::TooManyRequests(error)
+            | UpdateError::Unreachable(error)
+            | UpdateError::ParseError(error)
+            | UpdateError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<DeleteError> for Error {
+    fn from(error: DeleteError) -> Self {
+        match error {
+            DeleteError::BadRequest(error)
+            | DeleteError::Unauthorized(error)
+            | DeleteError::Forbidden(error)
+            | DeleteError::NotFound(error)
+            | DeleteError::TooManyRequests(error)
+            | DeleteError::Unreachable(error)
+            | DeleteError::UnexpectedResponse(error) => error,
+        }
+    }
+}
+
+impl From<RealtimeError> for Error {
+    fn from(error: RealtimeError) -> Self {
+        match error {
+            RealtimeError::Unreachable(error)
+            | RealtimeError::BadRequest(error)
+            | RealtimeError::Forbidden(error)
+            | RealtimeError::NotFound(error)
+            | RealtimeError::ParseError(error)
+            | RealtimeError::UnexpectedResponse(error) => error,
+            RealtimeError::Disconnected => Self::new(ErrorKind::Unreachable, error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    use super::*;
+
+    /// Returns the error built from a response with `status` and the JSON `body`.
+    #[allow(clippy::future_not_send)]
+    async fn error_from(status: u16, body: serde_json::Value) -> Error {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.path("/");
+                then.status(status).json_body(body);
+            })
+            .await;
+
+        let response = reqwest::get(server.base_url()).await.unwrap();
+
+        Error::from_response(response).await
+    }
+
+    #[tokio::test]
+    async fn reads_the_message_and_field_errors() {
+        let error = error_from(
+            400,
+            json!({
+                "status": 400,
+                "message": "Failed to create record.",
+                "data": {
+                    "title": { "code": "validation_required", "message": "Missing required value." },
+                    "note": "not a field error",
+                }
+            }),
+        )
+        .await;
+
+        assert_eq!(error.kind(), ErrorKind::BadRequest);
+        assert_eq!(error.status(), Some(reqwest::StatusCode::BAD_REQUEST));
+        assert_eq!(error.message(), "Failed to create record.");
+        assert_eq!(
+            error.data(),
+            [BadRequestError {
+                name: "title".to_string(),
+                code: "validation_required".to_string(),
+                message: "Missing required value.".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_the_legacy_code_field() {
+        let error = error_from(
+            404,
+            json!({ "code": 404, "message": "The requested resource wasn't found.", "data": {} }),
+        )
+        .await;
+
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+        assert_eq!(error.message(), "The requested resource wasn't found.");
+        assert!(error.data().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reads_the_mfa_challenge() {
+        let error = error_from(401, json!({ "mfaId": "mfa_123" })).await;
+
+        assert_eq!(error.kind(), ErrorKind::MfaRequired);
+        assert_eq!(error.mfa_id(), Some("mfa_123"));
+    }
 }
@@ -0,0 +1,149 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::error::{Error, ErrorKind, RequestError};
+
+/// The maximum depth of nested relations expanded by `PocketBase`.
+pub const MAX_EXPAND_DEPTH: usize = 6;
+
+/// The relations to expand, rendered to the `PocketBase` expand syntax
+/// *(example: `author.company,tags`)*.
+///
+/// An `Expand` can be given to the `expand` method of the [`crate::Collection::get_one`],
+/// [`crate::Collection::get_list`], [`crate::Collection::get_first_list_item`] and
+/// [`crate::Collection::get_full_list`] builders.
+///
+/// `PocketBase` expands up to 6 levels of nested relations. A deeper relation is rejected
+/// with a [`RequestError::BadRequest`] when the request is called, without being sent.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use pocketbase_rs::Expand;
+///
+/// let expand = Expand::rel("author").nested("company").and(Expand::rel("tags"));
+///
+/// assert_eq!(expand.to_string(), "author.company,tags");
+///
+/// let article = pb
+///     .collection("articles")?
+///     .get_one::<Article>("record_id_123")
+///     .expand(expand)
+///     .call()
+///     .await?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct Expand(Vec<Vec<String>>);
+
+impl Expand {
+    /// Expands the given relation field *(example: `"author"`, or `"comments_via_post"` for
+    /// a back-relation)*.
+    pub fn rel(field: impl Into<String>) -> Self {
+        Self(vec![vec![field.into()]])
+    }
+
+    /// Also expands the given relation field of the record expanded last.
+    pub fn nested(mut self, field: impl Into<String>) -> Self {
+        if let Some(path) = self.0.last_mut() {
+            path.push(field.into());
+        }
+
+        self
+    }
+
+    /// Also expands the relations of `other`.
+    pub fn and(mut self, other: Self) -> Self {
+        self.0.extend(other.0);
+        self
+    }
+
+    /// Returns the depth of the most nested relation, `1` when there is no nested relation.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.0.iter().map(Vec::len).max().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Expand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, path) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+
+            f.write_str(&path.join("."))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Expand> for Cow<'_, str> {
+    fn from(expand: Expand) -> Self {
+        Cow::Owned(expand.to_string())
+    }
+}
+
+/// Rejects an expand parameter nesting more than [`MAX_EXPAND_DEPTH`] relations.
+pub fn validate_expand_depth(expand: &str) -> Result<(), RequestError> {
+    let too_deep = expand
+        .split(',')
+        .find(|path| path.split('.').count() > MAX_EXPAND_DEPTH);
+
+    too_deep.map_or(Ok(()), |path| {
+        Err(RequestError::BadRequest(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "The expanded relation `{}` is nested deeper than {MAX_EXPAND_DEPTH} levels.",
+                path.trim()
+            ),
+        )))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_six_levels() {
+        assert!(validate_expand_depth("a.b.c.d.e.f").is_ok());
+        assert!(validate_expand_depth("author").is_ok());
+    }
+
+    #[test]
+    fn rejects_seven_levels() {
+        let Err(RequestError::BadRequest(error)) = validate_expand_depth("a.b.c.d.e.f.g") else {
+            panic!("a relation nested 7 levels deep should be rejected");
+        };
+
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn checks_every_path() {
+        assert!(validate_expand_depth("author.company,tags,a.b.c.d.e.f").is_ok());
+        assert!(validate_expand_depth("author.company,a.b.c.d.e.f.g,tags").is_err());
+    }
+
+    #[test]
+    fn trims_the_rejected_path() {
+        let Err(error) = validate_expand_depth("tags, a.b.c.d.e.f.g ") else {
+            panic!("a relation nested 7 levels deep should be rejected");
+        };
+
+        assert!(error.to_string().contains("`a.b.c.d.e.f.g`"));
+        assert!(validate_expand_depth("tags, a.b.c.d.e.f ").is_ok());
+    }
+
+    #[test]
+    fn renders_nested_relations() {
+        let expand = Expand::rel("author")
+            .nested("company")
+            .and(Expand::rel("tags"));
+
+        assert_eq!(expand.to_string(), "author.company,tags");
+        assert_eq!(expand.depth(), 2);
+    }
+}
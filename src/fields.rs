@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+
+/// The fields to return in the JSON response, rendered to the `PocketBase` fields syntax
+/// *(example: `id,title,content:excerpt(200,true)`)*.
+///
+/// A `Fields` can be given to the `fields` method of the [`crate::Collection::get_one`],
+/// [`crate::Collection::get_list`], [`crate::Collection::get_first_list_item`] and
+/// [`crate::Collection::get_full_list`] builders. When it is empty, every field is returned.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use pocketbase_rs::Fields;
+///
+/// #[derive(Default, Deserialize, Clone)]
+/// struct ArticlePreview {
+///     id: String,
+///     title: String,
+/// }
+///
+/// // Only download the fields of `ArticlePreview`, and an excerpt of the content.
+/// let fields = Fields::of::<ArticlePreview>().excerpt("content", 200, true);
+///
+/// assert_eq!(fields.to_string(), "id,title,content:excerpt(200,true)");
+///
+/// let articles = pb
+///     .collection("articles")?
+///     .get_list::<ArticlePreview>()
+///     .fields(Fields::of::<ArticlePreview>())
+///     .call()
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct Fields(Vec<String>);
+
+impl Fields {
+    /// Creates an empty list of fields, returning every field until one is added.
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Lists the fields of the struct `T`, as named by its `Deserialize` implementation
+    /// *(`#[serde(rename)]` included)*.
+    ///
+    /// The list is empty when `T` isn't a struct with named fields, for example a
+    /// `serde_json::Value` or a struct using `#[serde(flatten)]`.
+    pub fn of<T: DeserializeOwned>() -> Self {
+        let mut fields: &'static [&'static str] = &[];
+        let _ = T::deserialize(StructFields(&mut fields));
+
+        Self(fields.iter().map(ToString::to_string).collect())
+    }
+
+    /// Adds a field *(example: `"title"`, `"*"` for every field of the level, or
+    /// `"expand.author.name"`)*.
+    pub fn field(mut self, field: impl Into<String>) -> Self {
+        self.0.push(field.into());
+        self
+    }
+
+    /// Adds a text field, shortened to its first `max_length` characters with the `:excerpt`
+    /// modifier, and ending with `...` if `with_ellipsis` is set.
+    pub fn excerpt(mut self, field: impl AsRef<str>, max_length: u32, with_ellipsis: bool) -> Self {
+        self.0.push(format!(
+            "{}:excerpt({max_length},{with_ellipsis})",
+            field.as_ref()
+        ));
+        self
+    }
+
+    /// Returns `true` if no field was added, in which case every field is returned.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Fields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.join(","))
+    }
+}
+
+impl From<Fields> for Cow<'_, str> {
+    fn from(fields: Fields) -> Self {
+        Cow::Owned(fields.to_string())
+    }
+}
+
+/// A `Deserializer` reading the names of the fields of a struct, without deserializing it.
+struct StructFields<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for StructFields<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+
+        Err(de::Error::custom("fields read"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+        ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::Record;
+
+    #[derive(Deserialize)]
+    struct Article {
+        id: String,
+        title: String,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Profile {
+        display_name: String,
+        #[serde(rename = "avatar_url")]
+        avatar: String,
+    }
+
+    #[test]
+    fn lists_the_fields_of_a_struct() {
+        assert_eq!(Fields::of::<Article>().to_string(), "id,title");
+    }
+
+    #[test]
+    fn follows_serde_renames() {
+        assert_eq!(
+            Fields::of::<Profile>().to_string(),
+            "displayName,avatar_url"
+        );
+    }
+
+    #[test]
+    fn is_empty_for_flattened_records() {
+        // A flattened struct is read as a map, whose keys are unknown.
+        assert!(Fields::of::<Record<Article>>().is_empty());
+        assert!(Fields::of::<serde_json::Value>().is_empty());
+    }
+
+    #[test]
+    fn renders_excerpts() {
+        let fields = Fields::of::<Article>()
+            .excerpt("content", 200, true)
+            .excerpt("summary", 50, false)
+            .field("expand.author.name");
+
+        assert_eq!(
+            fields.to_string(),
+            "id,title,content:excerpt(200,true),summary:excerpt(50,false),expand.author.name"
+        );
+    }
+
+    #[test]
+    fn renders_nothing_when_empty() {
+        assert!(Fields::new().is_empty());
+        assert_eq!(Fields::new().to_string(), "");
+    }
+}
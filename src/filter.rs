@@ -0,0 +1,573 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::FilterError;
+
+/// A filter expression, rendered to the `PocketBase` filter syntax.
+///
+/// Values are quoted and escaped when rendered, so that they can come from user input without
+/// changing the meaning of the expression. A value that can't be written safely *(a text ending
+/// with a backslash, or a number that is not finite)* is rejected with a [`FilterError`] instead.
+/// Field names are written as is, and must not come from user input.
+///
+/// A `Filter` can be given to the `filter` method of the [`crate::Collection::get_list`],
+/// [`crate::Collection::get_first_list_item`] and [`crate::Collection::get_full_list`] builders.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use pocketbase_rs::Filter;
+///
+/// let filter = Filter::field("status")
+///     .eq("active")
+///     .and(Filter::field("views").gt(10).or(Filter::field("featured").eq(true)));
+///
+/// assert_eq!(
+///     filter.try_to_string()?,
+///     "status = 'active' && (views > 10 || featured = true)"
+/// );
+///
+/// let articles = pb
+///     .collection("articles")?
+///     .get_list::<Article>()
+///     .filter(filter)
+///     .call()
+///     .await?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct Filter(Expression);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expression {
+    Comparison {
+        field: String,
+        operator: &'static str,
+        value: FilterValue,
+    },
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+    Raw(String),
+}
+
+impl Filter {
+    /// Starts a comparison on the given field *(example: `"title"`, `"author.name"` or
+    /// `"@request.auth.id"`)*.
+    pub fn field(name: impl Into<String>) -> FilterField {
+        FilterField {
+            name: name.into(),
+            any: false,
+        }
+    }
+
+    /// Uses an expression already written in the `PocketBase` filter syntax.
+    ///
+    /// The expression is not escaped: it must not contain user input.
+    pub fn raw(expression: impl Into<String>) -> Self {
+        Self(Expression::Raw(expression.into()))
+    }
+
+    /// Combines both filters with `&&`, matching the records matched by both of them.
+    pub fn and(self, other: Self) -> Self {
+        match self.0 {
+            Expression::And(mut filters) => {
+                filters.push(other);
+                Self(Expression::And(filters))
+            }
+            expression => Self(Expression::And(vec![Self(expression), other])),
+        }
+    }
+
+    /// Combines both filters with `||`, matching the records matched by any of them.
+    pub fn or(self, other: Self) -> Self {
+        match self.0 {
+            Expression::Or(mut filters) => {
+                filters.push(other);
+                Self(Expression::Or(filters))
+            }
+            expression => Self(Expression::Or(vec![Self(expression), other])),
+        }
+    }
+
+    /// Renders the filter to the `PocketBase` filter syntax.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FilterError`] if one of the values can't be written safely: a text ending
+    /// with a backslash, which would escape its closing quote, or a number that is not finite.
+    pub fn try_to_string(&self) -> Result<String, FilterError> {
+        let mut expression = String::new();
+        self.write(&mut expression)?;
+
+        Ok(expression)
+    }
+
+    fn write(&self, out: &mut String) -> Result<(), FilterError> {
+        let (filters, separator) = match &self.0 {
+            Expression::Comparison {
+                field,
+                operator,
+                value,
+            } => {
+                // Writing to a `String` never fails.
+                let _ = write!(out, "{field} {operator} {}", value.render()?);
+                return Ok(());
+            }
+            Expression::Raw(expression) => {
+                out.push_str(expression);
+                return Ok(());
+            }
+            Expression::And(filters) => (filters, " && "),
+            Expression::Or(filters) => (filters, " || "),
+        };
+
+        for (index, filter) in filters.iter().enumerate() {
+            if index > 0 {
+                out.push_str(separator);
+            }
+
+            filter.write_grouped(out)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the filter, wrapped in brackets when it combines several expressions.
+    fn write_grouped(&self, out: &mut String) -> Result<(), FilterError> {
+        match &self.0 {
+            Expression::And(_) | Expression::Or(_) | Expression::Raw(_) => {
+                out.push('(');
+                self.write(out)?;
+                out.push(')');
+
+                Ok(())
+            }
+            Expression::Comparison { .. } => self.write(out),
+        }
+    }
+}
+
+/// A filter expression given to the `filter` method of the [`crate::Collection::get_list`],
+/// [`crate::Collection::get_first_list_item`] and [`crate::Collection::get_full_list`] builders.
+///
+/// It is created from a string already written in the `PocketBase` filter syntax, or from a
+/// [`Filter`]. A [`Filter`] that can't be rendered is rejected with a
+/// [`crate::RequestError::BadRequest`] when the request is called, without being sent.
+#[derive(Debug, Clone)]
+pub struct FilterExpression<'a>(Result<Cow<'a, str>, FilterError>);
+
+impl<'a> FilterExpression<'a> {
+    pub(crate) fn into_result(self) -> Result<Cow<'a, str>, FilterError> {
+        self.0
+    }
+}
+
+impl<'a> From<&'a str> for FilterExpression<'a> {
+    fn from(expression: &'a str) -> Self {
+        Self(Ok(Cow::Borrowed(expression)))
+    }
+}
+
+impl<'a> From<&'a String> for FilterExpression<'a> {
+    fn from(expression: &'a String) -> Self {
+        Self(Ok(Cow::Borrowed(expression)))
+    }
+}
+
+impl From<String> for FilterExpression<'_> {
+    fn from(expression: String) -> Self {
+        Self(Ok(Cow::Owned(expression)))
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for FilterExpression<'a> {
+    fn from(expression: Cow<'a, str>) -> Self {
+        Self(Ok(expression))
+    }
+}
+
+impl From<Filter> for FilterExpression<'_> {
+    fn from(filter: Filter) -> Self {
+        Self::from(&filter)
+    }
+}
+
+impl From<&Filter> for FilterExpression<'_> {
+    fn from(filter: &Filter) -> Self {
+        Self(filter.try_to_string().map(Cow::Owned))
+    }
+}
+
+/// A field of a [`Filter`], waiting for its comparison.
+///
+/// Created by [`Filter::field`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct FilterField {
+    name: String,
+    any: bool,
+}
+
+impl FilterField {
+    /// Matches when *at least one* of the values of a multiple field *(relation, select, file,
+    /// or a back-relation)* satisfies the comparison, using the `?` operators.
+    pub const fn any(mut self) -> Self {
+        self.any = true;
+        self
+    }
+
+    /// Equal (`=`).
+    pub fn eq(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("=", "?=", value.into())
+    }
+
+    /// NOT equal (`!=`).
+    pub fn ne(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("!=", "?!=", value.into())
+    }
+
+    /// Greater than (`>`).
+    pub fn gt(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare(">", "?>", value.into())
+    }
+
+    /// Greater than or equal (`>=`).
+    pub fn gte(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare(">=", "?>=", value.into())
+    }
+
+    /// Less than (`<`).
+    pub fn lt(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("<", "?<", value.into())
+    }
+
+    /// Less than or equal (`<=`).
+    pub fn lte(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("<=", "?<=", value.into())
+    }
+
+    /// Like/Contains (`~`).
+    ///
+    /// The value is wrapped in `%` by `PocketBase` for a wildcard match, unless it already
+    /// contains a `%`.
+    pub fn like(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("~", "?~", value.into())
+    }
+
+    /// NOT Like/Contains (`!~`).
+    ///
+    /// The value is wrapped in `%` by `PocketBase` for a wildcard match, unless it already
+    /// contains a `%`.
+    pub fn not_like(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare("!~", "?!~", value.into())
+    }
+
+    /// Matches when the field is empty or not set (`= null`).
+    pub fn is_null(self) -> Filter {
+        self.compare("=", "?=", FilterValue::Null)
+    }
+
+    /// Matches when the field is set (`!= null`).
+    pub fn is_not_null(self) -> Filter {
+        self.compare("!=", "?!=", FilterValue::Null)
+    }
+
+    fn compare(
+        self,
+        operator: &'static str,
+        any_operator: &'static str,
+        value: FilterValue,
+    ) -> Filter {
+        Filter(Expression::Comparison {
+            field: self.name,
+            operator: if self.any { any_operator } else { operator },
+            value,
+        })
+    }
+}
+
+/// The right operand of a [`Filter`] comparison.
+///
+/// Usually created from a Rust value: strings, numbers, booleans, `DateTime<Utc>`, and `Option`
+/// of them, where `None` is `null`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    /// `null`, also matching empty values.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// An integer.
+    Int(i64),
+    /// An unsigned integer, too large for an [`FilterValue::Int`].
+    UInt(u64),
+    /// A floating point number, rejected when rendered if it is not finite.
+    Float(f64),
+    /// A string, quoted and escaped when rendered.
+    ///
+    /// A string ending with a backslash is rejected when rendered: `PocketBase` would read the
+    /// backslash as escaping the closing quote.
+    Text(String),
+    /// Another field, or a macro such as `@now` or `@request.auth.id`, written as is.
+    ///
+    /// It must not contain user input.
+    Field(String),
+}
+
+impl FilterValue {
+    /// Compares against another field, or a macro such as `@now` or `@request.auth.id`.
+    ///
+    /// The name is not escaped: it must not contain user input.
+    pub fn field(name: impl Into<String>) -> Self {
+        Self::Field(name.into())
+    }
+
+    fn render(&self) -> Result<Cow<'_, str>, FilterError> {
+        match self {
+            Self::Null => Ok(Cow::Borrowed("null")),
+            Self::Bool(value) => Ok(Cow::Owned(value.to_string())),
+            Self::Int(value) => Ok(Cow::Owned(value.to_string())),
+            Self::UInt(value) => Ok(Cow::Owned(value.to_string())),
+            Self::Float(value) if value.is_finite() => Ok(Cow::Owned(value.to_string())),
+            Self::Float(value) => Err(FilterError::NonFiniteNumber(*value)),
+            // The filter parser has no escape for the backslash itself, only for the quote.
+            Self::Text(value) if value.ends_with('\\') => {
+                Err(FilterError::UnquotableText(value.clone()))
+            }
+            Self::Text(value) => Ok(Cow::Owned(format!("'{}'", value.replace('\'', "\\'")))),
+            Self::Field(name) => Ok(Cow::Borrowed(name)),
+        }
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&String> for FilterValue {
+    fn from(value: &String) -> Self {
+        Self::Text(value.clone())
+    }
+}
+
+impl From<bool> for FilterValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i8> for FilterValue {
+    fn from(value: i8) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<i16> for FilterValue {
+    fn from(value: i16) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<i32> for FilterValue {
+    fn from(value: i32) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<i64> for FilterValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u8> for FilterValue {
+    fn from(value: u8) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<u16> for FilterValue {
+    fn from(value: u16) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<u32> for FilterValue {
+    fn from(value: u32) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<u64> for FilterValue {
+    fn from(value: u64) -> Self {
+        Self::UInt(value)
+    }
+}
+
+impl From<usize> for FilterValue {
+    fn from(value: usize) -> Self {
+        Self::UInt(value as u64)
+    }
+}
+
+impl From<f32> for FilterValue {
+    fn from(value: f32) -> Self {
+        Self::Float(value.into())
+    }
+}
+
+impl From<f64> for FilterValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<DateTime<Utc>> for FilterValue {
+    /// Formats the date like `PocketBase` stores it *(example: `'2024-01-31 12:00:00.000Z'`)*.
+    fn from(value: DateTime<Utc>) -> Self {
+        Self::Text(value.format("%Y-%m-%d %H:%M:%S%.3fZ").to_string())
+    }
+}
+
+impl<T: Into<Self>> From<Option<T>> for FilterValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn render(filter: &Filter) -> String {
+        filter.try_to_string().unwrap()
+    }
+
+    #[test]
+    fn renders_values() {
+        assert_eq!(render(&Filter::field("title").eq("fox")), "title = 'fox'");
+        assert_eq!(render(&Filter::field("views").gt(10)), "views > 10");
+        assert_eq!(
+            render(&Filter::field("views").lte(u64::MAX)),
+            "views <= 18446744073709551615"
+        );
+        assert_eq!(render(&Filter::field("index").eq(3_usize)), "index = 3");
+        assert_eq!(render(&Filter::field("rating").gte(4.5)), "rating >= 4.5");
+        assert_eq!(
+            render(&Filter::field("featured").ne(true)),
+            "featured != true"
+        );
+        assert_eq!(
+            render(&Filter::field("author").eq(None::<&str>)),
+            "author = null"
+        );
+        assert_eq!(
+            render(&Filter::field("author").is_not_null()),
+            "author != null"
+        );
+        assert_eq!(
+            render(&Filter::field("owner").eq(FilterValue::field("@request.auth.id"))),
+            "owner = @request.auth.id"
+        );
+        assert_eq!(
+            render(
+                &Filter::field("created").lt(Utc.with_ymd_and_hms(2024, 1, 31, 12, 0, 0).unwrap())
+            ),
+            "created < '2024-01-31 12:00:00.000Z'"
+        );
+        assert_eq!(
+            render(&Filter::field("tags").any().like("rust")),
+            "tags ?~ 'rust'"
+        );
+    }
+
+    #[test]
+    fn escapes_quotes() {
+        assert_eq!(
+            render(&Filter::field("title").eq("it's")),
+            r"title = 'it\'s'"
+        );
+        assert_eq!(
+            render(&Filter::field("title").eq("' || id != '")),
+            r"title = '\' || id != \''"
+        );
+        // The parser unescapes `\'` to `'`: `a\'` is written `a\\'`.
+        assert_eq!(
+            render(&Filter::field("title").eq(r"a\'")),
+            r"title = 'a\\''"
+        );
+        assert_eq!(
+            render(&Filter::field("path").eq(r"C:\temp")),
+            r"path = 'C:\temp'"
+        );
+    }
+
+    #[test]
+    fn rejects_trailing_backslash() {
+        let filter = Filter::field("title")
+            .eq(r"x\")
+            .and(Filter::field("status").eq(" || id != "));
+
+        assert_eq!(
+            filter.try_to_string(),
+            Err(FilterError::UnquotableText(r"x\".to_string()))
+        );
+        assert!(FilterExpression::from(filter).into_result().is_err());
+    }
+
+    #[test]
+    fn rejects_non_finite_numbers() {
+        assert!(matches!(
+            Filter::field("rating").eq(f64::NAN).try_to_string(),
+            Err(FilterError::NonFiniteNumber(value)) if value.is_nan()
+        ));
+        assert_eq!(
+            Filter::field("rating").lt(f64::INFINITY).try_to_string(),
+            Err(FilterError::NonFiniteNumber(f64::INFINITY))
+        );
+        assert_eq!(
+            Filter::field("rating")
+                .gt(f32::NEG_INFINITY)
+                .try_to_string(),
+            Err(FilterError::NonFiniteNumber(f64::NEG_INFINITY))
+        );
+    }
+
+    #[test]
+    fn groups_combined_filters() {
+        let filter = Filter::field("status").eq("active").and(
+            Filter::field("views")
+                .gt(10)
+                .or(Filter::field("featured").eq(true)),
+        );
+
+        assert_eq!(
+            render(&filter),
+            "status = 'active' && (views > 10 || featured = true)"
+        );
+
+        let filter = Filter::field("a")
+            .eq(1)
+            .and(Filter::field("b").eq(2))
+            .and(Filter::field("c").eq(3))
+            .or(Filter::field("d").eq(4));
+
+        assert_eq!(render(&filter), "(a = 1 && b = 2 && c = 3) || d = 4");
+
+        let filter = Filter::raw("a = 1 || b = 2").and(Filter::field("c").eq(3));
+
+        assert_eq!(render(&filter), "(a = 1 || b = 2) && c = 3");
+        assert_eq!(render(&Filter::raw("a = 1 || b = 2")), "a = 1 || b = 2");
+    }
+}
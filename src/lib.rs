@@ -16,15 +16,15 @@
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn Error>> {
-//!     let mut pb = PocketBase::new("http://localhost:8090");
+//!     let pb = PocketBase::new("http://localhost:8090");
 //!
 //!     let auth_data = pb
-//!         .collection("users")
+//!         .collection("users")?
 //!         .auth_with_password("test@domain.com", "secure-password")
 //!         .await?;
 //!
 //!     let article: Article = pb
-//!         .collection("articles")
+//!         .collection("articles")?
 //!         .get_one::<Article>("record_id_123")
 //!         .call()
 //!         .await?;
@@ -55,7 +55,17 @@
 //! - Authorization errors (forbidden access)
 //! - Rate limiting errors
 //!
+//! Each of them converts into [`Error`], which keeps the HTTP status, the message and the field
+//! errors returned by `PocketBase`, and can be used as the single error type of an application.
+//!
 //! Always handle errors appropriately and avoid exposing sensitive error details to end users.
+//!
+//! # Tracing
+//!
+//! With the `tracing` feature, every API call emits a span named after the operation
+//! *(`get_one`, `auth_with_password`, ...)*, with the `collection`, the `record_id` when there
+//! is one, the response `status` and the `latency_ms`. Tokens, passwords and request bodies
+//! are never logged.
 
 #![deny(missing_docs)]
 #![warn(clippy::nursery)]
@@ -63,14 +73,45 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(dead_code)]
 
+pub use builder::PocketBaseBuilder;
 pub use error::*;
+pub use expand::Expand;
+pub use fields::Fields;
+pub use filter::{Filter, FilterExpression, FilterField, FilterValue};
+pub use middleware::Middleware;
+pub use realtime::subscribe::{RecordAction, RecordEvent, Subscription, SubscriptionEvent};
+pub use realtime::Realtime;
+pub use records::auth::auth_store_backend::{AuthStoreBackend, FileAuthStore, MemoryAuthStore};
+pub use records::auth::auth_with_oauth2::{OAuth2AuthResponse, OAuth2Meta};
+pub use records::auth::list_auth_methods::{
+    AuthMethodsList, AuthProviderInfo, MfaAuthMethod, OAuth2AuthMethod, OtpAuthMethod,
+    PasswordAuthMethod,
+};
+pub use records::auth::request_otp::OtpRequest;
 pub use records::auth::{AuthStore, AuthStoreRecord};
+pub use retry::RetryPolicy;
+pub use sort::Sort;
+
+use std::borrow::Cow;
+
+use reqwest::header::{HeaderValue, AUTHORIZATION};
 pub use reqwest::multipart::{Form, Part};
-use reqwest::RequestBuilder;
+use reqwest::{RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
 
+pub(crate) mod builder;
 pub mod error;
+pub(crate) mod expand;
+pub(crate) mod fields;
+pub(crate) mod filter;
+pub(crate) mod middleware;
+pub(crate) mod realtime;
 pub(crate) mod records;
+pub(crate) mod response;
+pub(crate) mod retry;
+pub(crate) mod sort;
+#[cfg(feature = "tracing")]
+pub(crate) mod trace;
 
 /// Represents a specific collection in a `PocketBase` database.
 ///
@@ -81,12 +122,12 @@ pub(crate) mod records;
 /// this struct.
 ///
 /// # Fields
-/// - `client`: A mutable reference to the `PocketBase` client instance.
+/// - `client`: A reference to the `PocketBase` client instance.
 ///   This allows the `Collection` to send requests to the `PocketBase` server.
 /// - `name`: The name of the collection being interacted with.
 pub struct Collection<'a> {
-    pub(crate) client: &'a mut PocketBase,
-    pub(crate) name: &'a str,
+    pub(crate) client: &'a PocketBase,
+    pub(crate) name: Cow<'a, str>,
 }
 
 impl PocketBase {
@@ -97,48 +138,64 @@ impl PocketBase {
     /// by this method.
     ///
     /// # Arguments
-    /// * `collection_name` - The name of the collection to interact with, provided as a static string.
+    /// * `collection_name` - The name of the collection to interact with, either borrowed *(example: `"articles"`)*
+    ///   or owned *(example: a `String` read from configuration)*.
     ///
     /// # Returns
     /// A [`Collection`] instance configured for the specified collection.
     ///
     /// # Example
     ///
-    /// ```
-    /// let mut client = PocketBase::new("http://localhost:8090");
+    /// ```rust,ignore
+    /// let pb = PocketBase::new("http://localhost:8090");
     ///
-    /// let collection = client.auth_with_password("use@domain.com", "super-secure-password");
+    /// // ...
     ///
     /// let request = pb
-    ///     .collection("articles")
+    ///     .collection("articles")?
     ///     .get_first_list_item::<Article>()
     ///     .filter("language='en'")
     ///     .call()
     ///     .await;
+    ///
+    /// let tenant_collection = format!("articles_{tenant_id}");
+    ///
+    /// let request = pb
+    ///     .collection(tenant_collection)?
+    ///     .get_first_list_item::<Article>()
+    ///     .call()
+    ///     .await;
     /// ```
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// This method will panic if the collection name is empty or contains invalid characters.
-    pub fn collection(&mut self, collection_name: &'static str) -> Collection {
-        // Validate collection name
-        assert!(
-            !collection_name.is_empty(),
-            "Collection name cannot be empty"
-        );
+    /// This method returns a [`CollectionNameError`] if the collection name is empty
+    /// or contains invalid characters.
+    pub fn collection<'a>(
+        &'a self,
+        collection_name: impl Into<Cow<'a, str>>,
+    ) -> Result<Collection<'a>, CollectionNameError> {
+        let collection_name = collection_name.into();
 
-        // Collection names should only contain alphanumeric characters and underscores
-        assert!(
-            collection_name
-                .chars()
-                .all(|c| c.is_alphanumeric() || c == '_'),
-            "Collection name contains invalid characters. Only alphanumeric characters and underscores are allowed"
-        );
+        if collection_name.is_empty() {
+            return Err(CollectionNameError::Empty);
+        }
 
-        Collection {
+        // Collection names should only contain alphanumeric characters and underscores,
+        // system collections starting with one (example: `_superusers`)
+        if !collection_name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_')
+        {
+            return Err(CollectionNameError::InvalidCharacters(
+                collection_name.into_owned(),
+            ));
+        }
+
+        Ok(Collection {
             client: self,
             name: collection_name,
-        }
+        })
     }
 }
 
@@ -176,17 +233,129 @@ pub struct RecordList<T> {
     pub items: Vec<T>,
 }
 
+/// A record of a `PocketBase` collection, with its system fields, its data and its expanded
+/// relations.
+///
+/// Expanded relations are returned under the `expand` JSON object, and can be typed with `E`.
+/// With the default `E = NoExpand`, the `expand` object is ignored.
+///
+/// # Type Parameters
+/// - `T`: The fields of the collection, flattened into the record. It should not contain the
+///   system fields nor `expand`, which are read by the record itself.
+/// - `E`: The expanded relations, read from `expand` *(missing relations must have a default
+///   value, for example with `Option`)*.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use pocketbase_rs::{Expand, Record};
+/// use serde::Deserialize;
+///
+/// #[derive(Default, Deserialize, Clone)]
+/// struct Article {
+///     title: String,
+///     author: String,
+/// }
+///
+/// #[derive(Default, Deserialize, Clone)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[derive(Default, Deserialize, Clone)]
+/// struct ArticleExpand {
+///     author: Option<Record<User>>,
+/// }
+///
+/// let article = pb
+///     .collection("articles")?
+///     .get_one::<Record<Article, ArticleExpand>>("record_id_123")
+///     .expand(Expand::rel("author"))
+///     .call()
+///     .await?;
+///
+/// println!("{} by {:?}", article.data.title, article.expand.author.map(|author| author.data.name));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(
+    rename_all = "camelCase",
+    bound(deserialize = "T: Deserialize<'de>, E: Deserialize<'de> + Default")
+)]
+pub struct Record<T, E = NoExpand> {
+    /// The ID of the record.
+    pub id: String,
+    /// The ID of the collection of the record.
+    #[serde(default)]
+    pub collection_id: String,
+    /// The name of the collection of the record.
+    #[serde(default)]
+    pub collection_name: String,
+    /// The creation date of the record *(example: `2024-01-31 12:00:00.000Z`)*.
+    #[serde(default)]
+    pub created: String,
+    /// The last update date of the record *(example: `2024-01-31 12:00:00.000Z`)*.
+    #[serde(default)]
+    pub updated: String,
+    /// The other fields of the record.
+    #[serde(flatten)]
+    pub data: T,
+    /// The expanded relations of the record.
+    #[serde(default)]
+    pub expand: E,
+}
+
+/// The expanded relations of a [`Record`] read without its `expand` object, the default `E`.
+///
+/// It deserializes from any value, so that a record fetched with expanded relations can still be
+/// read as a `Record<T>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoExpand;
+
+impl<'de> Deserialize<'de> for NoExpand {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde::de::IgnoredAny::deserialize(deserializer)?;
+
+        Ok(Self)
+    }
+}
+
 /// Response structure for API errors from `PocketBase`.
 #[derive(Deserialize, Debug)]
 pub(crate) struct ErrorResponse {
-    /// HTTP status code
-    pub code: u16,
+    /// HTTP status code *(named `code` before `PocketBase` v0.23)*
+    #[serde(alias = "code")]
+    pub status: u16,
     /// Error message from the server
     pub message: String,
     /// Additional error data, if any
+    #[serde(default)]
     pub data: Option<serde_json::Value>,
 }
 
+impl ErrorResponse {
+    /// Flattens the fields of `data` that caused the error into a list of [`BadRequestError`].
+    ///
+    /// Entries that are not field errors are skipped.
+    pub(crate) fn into_errors(self) -> Vec<BadRequestError> {
+        let Some(serde_json::Value::Object(fields)) = self.data else {
+            return Vec::new();
+        };
+
+        fields
+            .into_iter()
+            .filter_map(|(name, field)| {
+                let field = serde_json::from_value::<BadRequestField>(field).ok()?;
+
+                Some(BadRequestError {
+                    name,
+                    code: field.code,
+                    message: field.message,
+                })
+            })
+            .collect()
+    }
+}
+
 /// A `PocketBase` Client. You can use it to send requests to the `PocketBase` instance.
 ///
 /// # Example
@@ -205,12 +374,12 @@ pub(crate) struct ErrorResponse {
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn Error>> {
-///     let mut pb = PocketBaseAdminBuilder::new("http://localhost:8090");
+///     let pb = PocketBaseAdminBuilder::new("http://localhost:8090");
 ///
 ///     // ...
 ///
 ///     let request = pb
-///         .collection("test")
+///         .collection("test")?
 ///         .get_one::<Test>("record_id")
 ///         .call()
 ///         .await?;
@@ -221,24 +390,32 @@ pub(crate) struct ErrorResponse {
 /// }
 /// A `PocketBase` Client. You can use it to send requests to the `PocketBase` instance.
 ///
+/// The client is cheap to clone, and can be shared between tasks: clones share the same
+/// authentication session *(including its automatic refresh)* and realtime connection.
+///
 /// The `Debug` implementation for this struct redacts sensitive authentication data
 /// to prevent accidental exposure in logs.
 #[derive(Clone)]
 pub struct PocketBase {
     pub(crate) base_url: String,
-    pub(crate) auth_store: Option<AuthStore>,
+    pub(crate) auth: std::sync::Arc<records::auth::auth_state::AuthState>,
+    pub(crate) auto_refresh: Option<std::time::Duration>,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) middlewares: middleware::Middlewares,
     pub(crate) reqwest_client: reqwest::Client,
+    pub(crate) realtime: std::sync::Arc<realtime::RealtimeSlot>,
 }
 
 impl std::fmt::Debug for PocketBase {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PocketBase")
             .field("base_url", &self.base_url)
-            .field(
-                "auth_store",
-                &self.auth_store.as_ref().map(|_| "***REDACTED***"),
-            )
+            .field("auth_store", &self.auth.token().map(|_| "***REDACTED***"))
+            .field("auto_refresh", &self.auto_refresh)
+            .field("retry_policy", &self.retry_policy)
+            .field("middlewares", &self.middlewares)
             .field("reqwest_client", &"Client")
+            .field("realtime", &"Realtime")
             .finish()
     }
 }
@@ -249,6 +426,8 @@ impl PocketBase {
     /// This method initializes a new client that can be used to interact with a `PocketBase`
     /// instance can then be used to authenticate users, manage records, and perform other API operations.
     ///
+    /// Use [`PocketBase::builder`] to configure the client, or to handle an invalid URL without panicking.
+    ///
     /// # Arguments
     /// * `base_url` - A string slice representing the base URL of the `PocketBase` instance (e.g., `"http://localhost:8090"`).
     ///
@@ -256,48 +435,33 @@ impl PocketBase {
     /// A `PocketBase` client instance that can be used to make requests to the `PocketBase` server.
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,ignore
     /// let client = PocketBase::new("http://localhost:8090");
     /// // Use the client for further operations like authentication or fetching records
     /// ```
     /// # Panics
     ///
-    /// This method will panic if the provided `base_url` is not a valid URL.
+    /// This method will panic if the provided `base_url` is not a valid `http` or `https` URL
+    /// without query string nor fragment, or if the HTTP client couldn't be initialized.
     #[must_use]
     pub fn new(base_url: &str) -> Self {
-        // Validate URL format
-        let trimmed_url = base_url.trim_end_matches('/');
-        assert!(
-            trimmed_url.starts_with("http://") || trimmed_url.starts_with("https://"),
-            "Invalid base_url: must start with http:// or https://"
-        );
-
-        // Create client with sensible defaults
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .connect_timeout(std::time::Duration::from_secs(10))
-            .build()
-            .expect("Failed to create HTTP client");
-
-        Self {
-            base_url: trimmed_url.to_string(),
-            auth_store: None,
-            reqwest_client: client,
+        match Self::builder(base_url).build() {
+            Ok(pocketbase) => pocketbase,
+            Err(error) => panic!("{error}"),
         }
     }
 
     /// Creates a new `PocketBase` client with a custom reqwest client.
     ///
     /// This method allows you to provide your own configured `reqwest::Client` instance,
-    /// which is useful when you need custom timeout settings, proxy configuration,
-    /// or other HTTP client customizations.
+    /// which is useful when you need HTTP client customizations not offered by [`PocketBase::builder`].
     ///
     /// # Arguments
     /// * `base_url` - A string slice representing the base URL of the `PocketBase` instance
     /// * `client` - A pre-configured `reqwest::Client` instance
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,ignore
     /// use std::time::Duration;
     ///
     /// let client = reqwest::Client::builder()
@@ -310,20 +474,23 @@ impl PocketBase {
     ///
     /// # Panics
     ///
-    /// This method will panic if the provided `base_url` is not a valid URL.
+    /// This method will panic if the provided `base_url` is not a valid `http` or `https` URL
+    /// without query string nor fragment.
     #[must_use]
     pub fn new_with_client(base_url: &str, client: reqwest::Client) -> Self {
-        // Validate URL format
-        let trimmed_url = base_url.trim_end_matches('/');
-        assert!(
-            trimmed_url.starts_with("http://") || trimmed_url.starts_with("https://"),
-            "Invalid base_url: must start with http:// or https://"
-        );
+        let base_url = match builder::parse_base_url(base_url) {
+            Ok(base_url) => base_url,
+            Err(error) => panic!("{error}"),
+        };
 
         Self {
-            base_url: trimmed_url.to_string(),
-            auth_store: None,
+            base_url,
+            auth: std::sync::Arc::default(),
+            auto_refresh: None,
+            retry_policy: None,
+            middlewares: middleware::Middlewares::default(),
             reqwest_client: client,
+            realtime: std::sync::Arc::default(),
         }
     }
 
@@ -339,7 +506,7 @@ impl PocketBase {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```rust,ignore
     /// let client = PocketBase::new("http://localhost:8090");
     ///
     /// // ...
@@ -352,7 +519,7 @@ impl PocketBase {
     /// ```
     #[must_use]
     pub fn auth_store(&self) -> Option<AuthStore> {
-        self.auth_store.clone()
+        self.auth.auth_store()
     }
 
     /// Retrieves the current authentication token, if available.
@@ -366,7 +533,7 @@ impl PocketBase {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```rust,ignore
     /// let pb = PocketBase::new("http://localhost:8090");
     ///
     /// // ...
@@ -379,9 +546,100 @@ impl PocketBase {
     /// ```
     #[must_use]
     pub fn token(&self) -> Option<String> {
-        self.auth_store
-            .as_ref()
-            .map(|auth_store| auth_store.token.clone())
+        self.auth.token()
+    }
+
+    /// Attaches a storage to the auth store, to keep the session across restarts.
+    ///
+    /// The session persisted by the backend, if any, replaces the current one. Afterwards,
+    /// every login and token refresh is written to the backend, and [`PocketBase::logout`]
+    /// clears it. Errors of the backend while writing don't fail the login or the logout, the
+    /// session staying usable in memory: they are given to the handler set with
+    /// [`PocketBase::on_auth_store_backend_error`], and logged as a `WARN` event with the
+    /// `tracing` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AuthStoreBackendError`] if the persisted session couldn't be loaded.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use pocketbase_rs::{FileAuthStore, PocketBase};
+    ///
+    /// let pb = PocketBase::new("http://localhost:8090");
+    ///
+    /// pb.set_auth_store_backend(FileAuthStore::new("session.json"))?;
+    ///
+    /// if pb.auth_store().is_none() {
+    ///     pb.collection("users")?
+    ///         .auth_with_password("test@domain.com", "secure-password")
+    ///         .await?;
+    /// }
+    /// ```
+    pub fn set_auth_store_backend(
+        &self,
+        backend: impl AuthStoreBackend + 'static,
+    ) -> Result<(), AuthStoreBackendError> {
+        self.auth.set_backend(std::sync::Arc::new(backend))?;
+        self.sync_realtime_auth_token();
+
+        Ok(())
+    }
+
+    /// Calls `handler` with the errors of the auth store backend while saving or clearing the
+    /// session, for example when the file of a [`FileAuthStore`] is read-only.
+    ///
+    /// This setting applies to this client and its clones, and replaces the previous handler.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use pocketbase_rs::{FileAuthStore, PocketBase};
+    ///
+    /// let pb = PocketBase::new("http://localhost:8090");
+    ///
+    /// pb.set_auth_store_backend(FileAuthStore::new("session.json"))?;
+    /// pb.on_auth_store_backend_error(|error| eprintln!("The session wasn't saved: {error}"));
+    /// ```
+    pub fn on_auth_store_backend_error(
+        &self,
+        handler: impl Fn(&AuthStoreBackendError) + Send + Sync + 'static,
+    ) {
+        self.auth.set_error_handler(std::sync::Arc::new(handler));
+    }
+
+    /// Logs out: removes the auth store of the client, and clears its storage backend.
+    ///
+    /// Subsequent requests are sent without authorization token.
+    pub fn logout(&self) {
+        self.auth.clear();
+        self.sync_realtime_auth_token();
+    }
+
+    /// Returns a receiver notified when the auth store changes.
+    ///
+    /// The receiver is notified on login, token refresh and logout, with the new auth store
+    /// *(`None` after a logout)*. Any number of receivers can be created, for example one per
+    /// component reacting to the session.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut changes = pb.auth_store_changes();
+    ///
+    /// tokio::spawn(async move {
+    ///     while changes.changed().await.is_ok() {
+    ///         match &*changes.borrow_and_update() {
+    ///             Some(auth_store) => println!("Logged in as {}", auth_store.record.email),
+    ///             None => println!("Logged out"),
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    #[must_use]
+    pub fn auth_store_changes(&self) -> tokio::sync::watch::Receiver<Option<AuthStore>> {
+        self.auth.subscribe()
     }
 
     /// Returns the base URL of the `PocketBase` server.
@@ -394,7 +652,7 @@ impl PocketBase {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```rust,ignore
     /// let client = PocketBase::new("http://localhost:8090");
     /// assert_eq!(client.base_url(), "http://localhost:8090".to_string());
     /// ```
@@ -403,8 +661,9 @@ impl PocketBase {
         self.base_url.clone()
     }
 
-    pub(crate) fn update_auth_store(&mut self, new_auth_store: AuthStore) {
-        self.auth_store = Some(new_auth_store);
+    pub(crate) fn update_auth_store(&self, new_auth_store: AuthStore) {
+        self.auth.set(new_auth_store, true);
+        self.sync_realtime_auth_token();
     }
 }
 
@@ -424,13 +683,127 @@ impl PocketBase {
         &self,
         request_builder: reqwest::RequestBuilder,
     ) -> reqwest::RequestBuilder {
-        if let Some(auth_store) = self.auth_store() {
-            request_builder.bearer_auth(auth_store.token)
+        if let Some(token) = self.token() {
+            request_builder.bearer_auth(token)
         } else {
             request_builder
         }
     }
 
+    /// Sends a request built with one of the `request_*` methods.
+    ///
+    /// When the automatic token refresh is enabled, the token is refreshed shortly before it
+    /// expires, and once after a `401 Unauthorized` response before sending the request again.
+    ///
+    /// Authentication requests are sent directly instead, as a `401` is part of their flow.
+    ///
+    /// Idempotent requests are retried according to the [`RetryPolicy`] of the client, if any,
+    /// and every request goes through the [`Middleware`]s of the client.
+    ///
+    /// # Arguments
+    /// * `request_builder` - A `reqwest::RequestBuilder` created by this client.
+    ///
+    /// # Returns
+    /// The `reqwest::Response`, or the `reqwest::Error` of the request.
+    pub(crate) async fn send(&self, request_builder: RequestBuilder) -> reqwest::Result<Response> {
+        self.dispatch(request_builder, self.auto_refresh.is_some())
+            .await
+    }
+
+    /// Same as [`PocketBase::send`], without the automatic token refresh.
+    pub(crate) async fn send_without_refresh(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> reqwest::Result<Response> {
+        self.dispatch(request_builder, false).await
+    }
+
+    /// Sends the request through the middlewares of the client, refreshing the token if asked.
+    async fn dispatch(
+        &self,
+        request_builder: RequestBuilder,
+        refresh: bool,
+    ) -> reqwest::Result<Response> {
+        let (client, request) = request_builder.build_split();
+        let mut request = request?;
+
+        // The token is set before the middlewares run, so that they can replace it.
+        let token = if refresh {
+            self.refresh_auth_if_expiring().await;
+            self.authorize(&mut request)
+        } else {
+            None
+        };
+
+        self.middlewares.before_send(&mut request);
+
+        let method = request.method().clone();
+        let started_at = std::time::Instant::now();
+
+        let response = if refresh {
+            self.send_with_refresh(&client, request, token).await
+        } else {
+            self.execute(&client, request).await
+        };
+
+        #[cfg(feature = "tracing")]
+        trace::record_response(&method, &response, started_at.elapsed());
+
+        let response = response?;
+
+        self.middlewares
+            .after_send(&method, &response, started_at.elapsed());
+
+        Ok(response)
+    }
+
+    /// Sends the request authorized with `token`, refreshing the token and sending the request
+    /// again after a `401 Unauthorized`.
+    async fn send_with_refresh(
+        &self,
+        client: &reqwest::Client,
+        request: reqwest::Request,
+        token: Option<String>,
+    ) -> reqwest::Result<Response> {
+        // A token replaced by a middleware isn't the one of the client, and isn't refreshed.
+        let token = token.filter(|token| {
+            request
+                .headers()
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                == Some(token.as_str())
+        });
+
+        let retry = request.try_clone();
+        let response = self.execute(client, request).await?;
+
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        // Multipart requests can't be cloned, and are not retried.
+        match (token, retry) {
+            (Some(token), Some(mut retry)) if self.refresh_auth(&token).await => {
+                self.authorize(&mut retry);
+                self.execute(client, retry).await
+            }
+            _ => Ok(response),
+        }
+    }
+
+    /// Replaces the authorization token of the request with the current one, and returns it.
+    fn authorize(&self, request: &mut reqwest::Request) -> Option<String> {
+        let token = self.token()?;
+
+        if let Ok(mut value) = HeaderValue::from_str(&format!("Bearer {token}")) {
+            value.set_sensitive(true);
+            request.headers_mut().insert(AUTHORIZATION, value);
+        }
+
+        Some(token)
+    }
+
     /// Creates a POST request builder for the specified endpoint.
     ///
     /// This method initializes a `POST` request to the given endpoint and adds
@@ -457,7 +830,7 @@ impl PocketBase {
     ///
     /// # Returns
     /// A `reqwest::RequestBuilder` for the `PATCH` request.
-    pub(crate) fn request_patch_json<T: Default + Serialize + Clone + Send>(
+    pub(crate) fn request_patch_json<T: Serialize + ?Sized>(
         &self,
         endpoint: &str,
         params: &T,
@@ -486,6 +859,22 @@ impl PocketBase {
         self.with_authorization_token(request_builder)
     }
 
+    /// Creates a PATCH request builder with a form body for the specified endpoint.
+    ///
+    /// This method initializes a `PATCH` request to the given endpoint with a multipart form body,
+    /// and adds an authorization token if available.
+    ///
+    /// # Arguments
+    /// * `endpoint` - The API endpoint to send the `PATCH` request to.
+    /// * `form` - A `reqwest::multipart::Form` representing the form data for the request.
+    ///
+    /// # Returns
+    /// A `reqwest::RequestBuilder` for the `PATCH` request.
+    pub(crate) fn request_patch_form(&self, endpoint: &str, form: Form) -> RequestBuilder {
+        let request_builder = self.reqwest_client.patch(endpoint).multipart(form);
+        self.with_authorization_token(request_builder)
+    }
+
     /// Creates a POST request builder with a form body for the specified endpoint.
     ///
     /// This method initializes a `POST` request to the given endpoint with a multipart form body,
@@ -544,7 +933,7 @@ impl PocketBase {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```rust,ignore
     /// let client = PocketBase::new("http://localhost:8090");
     ///
     /// let request = client.request_delete("http://localhost:8090/api/collections/articles/record_id");
@@ -555,3 +944,108 @@ impl PocketBase {
         self.with_authorization_token(request_builder)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+    struct Article {
+        title: String,
+        author: String,
+    }
+
+    #[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+    struct User {
+        name: String,
+    }
+
+    #[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+    struct ArticleExpand {
+        author: Option<Record<User>>,
+    }
+
+    fn article(expand: Option<serde_json::Value>) -> serde_json::Value {
+        let mut article = json!({
+            "id": "a1",
+            "collectionId": "pbc_articles",
+            "collectionName": "articles",
+            "created": "2024-01-31 12:00:00.000Z",
+            "updated": "2024-01-31 12:00:00.000Z",
+            "title": "Foxes",
+            "author": "u1",
+        });
+
+        if let Some(expand) = expand {
+            article["expand"] = expand;
+        }
+
+        article
+    }
+
+    fn author() -> serde_json::Value {
+        json!({
+            "author": {
+                "id": "u1",
+                "collectionId": "_pb_users_auth_",
+                "collectionName": "users",
+                "created": "2024-01-30 12:00:00.000Z",
+                "updated": "2024-01-30 12:00:00.000Z",
+                "name": "Vulpes",
+            }
+        })
+    }
+
+    #[test]
+    fn deserializes_a_record_without_expand() {
+        let record: Record<Article> = serde_json::from_value(article(None)).unwrap();
+
+        assert_eq!(record.id, "a1");
+        assert_eq!(record.collection_name, "articles");
+        assert_eq!(record.data.title, "Foxes");
+
+        let record: Record<Article, ArticleExpand> = serde_json::from_value(article(None)).unwrap();
+
+        assert_eq!(record.expand, ArticleExpand::default());
+    }
+
+    #[test]
+    fn deserializes_a_record_with_expand() {
+        let record: Record<Article, ArticleExpand> =
+            serde_json::from_value(article(Some(author()))).unwrap();
+
+        assert_eq!(record.data.author, "u1");
+        assert_eq!(record.expand.author.unwrap().data.name, "Vulpes");
+    }
+
+    #[test]
+    fn ignores_expand_by_default() {
+        let record: Record<Article> = serde_json::from_value(article(Some(author()))).unwrap();
+
+        assert_eq!(record.data.title, "Foxes");
+        assert_eq!(record.expand, NoExpand);
+    }
+
+    #[test]
+    fn validates_collection_names() {
+        let pb = PocketBase::new("http://localhost:8090");
+
+        assert_eq!(pb.collection("").err(), Some(CollectionNameError::Empty));
+        assert_eq!(
+            pb.collection("bad-name").err(),
+            Some(CollectionNameError::InvalidCharacters(
+                "bad-name".to_string()
+            ))
+        );
+        assert_eq!(pb.collection("_superusers").unwrap().name, "_superusers");
+
+        let name = format!("articles_{}", 2);
+        assert_eq!(pb.collection(name).unwrap().name, "articles_2");
+        assert_eq!(
+            pb.collection(String::from("a b")).err(),
+            Some(CollectionNameError::InvalidCharacters("a b".to_string()))
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::{Method, Request, Response};
+
+use crate::PocketBase;
+
+/// A hook called around every request sent by a [`PocketBase`] client, similar to the
+/// `beforeSend` and `afterSend` hooks of the JavaScript SDK.
+///
+/// It can be used to add tracing headers, to audit-log mutations or to rewrite requests.
+///
+/// Both methods do nothing by default, so that a middleware only implements the one it needs.
+/// Middlewares are called in the order they were added, each one receiving the request
+/// modified by the previous ones.
+///
+/// Record and authentication requests go through the middlewares, including the `auth-refresh`
+/// requests of the automatic token refresh, but not the realtime connection.
+///
+/// The `Authorization` header already holds the token of the client when
+/// [`Middleware::before_send`] is called, so that a middleware can replace it. A request
+/// whose token was replaced isn't retried with a refreshed token after a `401 Unauthorized`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+///
+/// use pocketbase_rs::{Middleware, PocketBase};
+/// use reqwest::{Method, Request, Response};
+///
+/// struct AuditLog;
+///
+/// impl Middleware for AuditLog {
+///     fn before_send(&self, request: &mut Request) {
+///         request
+///             .headers_mut()
+///             .insert("X-Request-Id", "b5c1b6e2".parse().unwrap());
+///     }
+///
+///     fn after_send(&self, method: &Method, response: &Response, elapsed: Duration) {
+///         if method != Method::GET {
+///             println!("{method} {} -> {} ({elapsed:?})", response.url(), response.status());
+///         }
+///     }
+/// }
+///
+/// let pb = PocketBase::builder("http://localhost:8090")
+///     .middleware(AuditLog)
+///     .build()?;
+/// ```
+pub trait Middleware: Send + Sync {
+    /// Inspects or modifies the request before it is sent.
+    ///
+    /// The request is already built, so that its method, URL, headers and body can be read.
+    /// It is called once, even when the request is sent again by the [`crate::RetryPolicy`]
+    /// or after a token refresh.
+    fn before_send(&self, request: &mut Request) {
+        let _ = request;
+    }
+
+    /// Inspects the raw response, before its body is decoded.
+    ///
+    /// `elapsed` is the time spent since the request was handed to the client, retries
+    /// included. It is not called when no response was received.
+    fn after_send(&self, method: &Method, response: &Response, elapsed: Duration) {
+        let _ = (method, response, elapsed);
+    }
+}
+
+/// The middlewares of a client, shared by its clones.
+#[derive(Clone, Default)]
+pub struct Middlewares(Vec<Arc<dyn Middleware>>);
+
+impl std::fmt::Debug for Middlewares {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} middleware(s)", self.0.len())
+    }
+}
+
+impl Middlewares {
+    /// Adds a middleware, called after the ones already added.
+    pub fn push(&mut self, middleware: impl Middleware + 'static) {
+        self.0.push(Arc::new(middleware));
+    }
+
+    /// Calls [`Middleware::before_send`] of every middleware, in order.
+    pub fn before_send(&self, request: &mut Request) {
+        for middleware in &self.0 {
+            middleware.before_send(request);
+        }
+    }
+
+    /// Calls [`Middleware::after_send`] of every middleware, in order.
+    pub fn after_send(&self, method: &Method, response: &Response, elapsed: Duration) {
+        for middleware in &self.0 {
+            middleware.after_send(method, response, elapsed);
+        }
+    }
+}
+
+impl PocketBase {
+    /// Adds a [`Middleware`] called around every request, after the ones already added.
+    ///
+    /// This setting applies to this client, and to the clones made afterwards.
+    pub fn add_middleware(&mut self, middleware: impl Middleware + 'static) {
+        self.middlewares.push(middleware);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use httpmock::prelude::*;
+    use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION};
+    use serde_json::json;
+
+    use super::*;
+
+    /// Records its calls in a log shared with the other middlewares of a test, and adds a
+    /// `header` listing the headers of the previous middlewares it received.
+    struct Recorder {
+        header: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Middleware for Recorder {
+        fn before_send(&self, request: &mut Request) {
+            let seen = ["x-first", "x-second"]
+                .into_iter()
+                .filter(|header| request.headers().contains_key(*header))
+                .collect::<Vec<_>>()
+                .join(",");
+
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} before", self.header));
+            request.headers_mut().insert(
+                HeaderName::from_static(self.header),
+                HeaderValue::from_str(&seen).unwrap(),
+            );
+        }
+
+        fn after_send(&self, method: &Method, response: &Response, elapsed: Duration) {
+            self.log.lock().unwrap().push(format!(
+                "{} after {method} {} {}",
+                self.header,
+                response.status().as_u16(),
+                elapsed >= Duration::from_millis(100),
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn calls_the_middlewares_in_order() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/api/health")
+                    .header("x-first", "")
+                    .header("x-second", "x-first");
+                then.status(200)
+                    .json_body(json!({}))
+                    .delay(Duration::from_millis(100));
+            })
+            .await;
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let mut pb = PocketBase::new(&server.base_url());
+        for header in ["x-first", "x-second"] {
+            pb.add_middleware(Recorder {
+                header,
+                log: log.clone(),
+            });
+        }
+
+        pb.send_and_decode::<serde_json::Value>(pb.request_get(&server.url("/api/health"), None))
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "x-first before",
+                "x-second before",
+                "x-first after GET 200 true",
+                "x-second after GET 200 true",
+            ]
+        );
+    }
+
+    /// Replaces the token of the client.
+    struct ApiKey;
+
+    impl Middleware for ApiKey {
+        fn before_send(&self, request: &mut Request) {
+            request
+                .headers_mut()
+                .insert(AUTHORIZATION, HeaderValue::from_static("Bearer api-key"));
+        }
+    }
+
+    #[tokio::test]
+    async fn keeps_the_authorization_set_by_a_middleware() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/api/health")
+                    .header("Authorization", "Bearer api-key");
+                then.status(401)
+                    .json_body(json!({ "status": 401, "message": "", "data": {} }));
+            })
+            .await;
+        let refresh = server
+            .mock_async(|when, then| {
+                when.path_contains("auth-refresh");
+                then.status(500);
+            })
+            .await;
+
+        let mut pb = PocketBase::new(&server.base_url());
+        pb.enable_auto_refresh(Duration::from_secs(60));
+        pb.add_middleware(ApiKey);
+        pb.update_auth_store(
+            serde_json::from_value(json!({
+                "token": "client-token",
+                "record": {
+                    "id": "u1",
+                    "collectionId": "_pb_users_auth_",
+                    "collectionName": "users",
+                    "created": "",
+                    "updated": "",
+                    "email": "fox@domain.com",
+                    "emailVisibility": false,
+                    "verified": true,
+                },
+            }))
+            .unwrap(),
+        );
+
+        let error = pb
+            .send_and_decode::<serde_json::Value>(pb.request_get(&server.url("/api/health"), None))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.kind(), crate::ErrorKind::Unauthorized);
+        assert_eq!(mock.hits_async().await, 1);
+        assert_eq!(refresh.hits_async().await, 0);
+    }
+}
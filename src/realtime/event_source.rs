@@ -0,0 +1,151 @@
+// Minimal Server-Sent Events parser, following the "Interpreting an event stream" section of
+// https://html.spec.whatwg.org/multipage/server-sent-events.html
+//
+// Only `\n` and `\r\n` line endings are handled, which is what PocketBase sends.
+
+/// A single event received from a Server-Sent Events stream.
+#[derive(Debug, Default)]
+pub struct Event {
+    /// Name of the event *(`message` when not specified by the server)*.
+    pub name: String,
+    /// Last event id sent by the server.
+    pub id: String,
+    /// Data of the event, with multiple `data` lines joined by `\n`.
+    pub data: String,
+}
+
+/// Incrementally turns the chunks of a Server-Sent Events body into [`Event`]s.
+#[derive(Debug, Default)]
+pub struct EventSource {
+    buffer: Vec<u8>,
+    name: String,
+    id: String,
+    data: String,
+}
+
+impl EventSource {
+    /// Feeds a new chunk of the response body, returning the events it completed.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Event> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = vec![];
+
+        while let Some(position) = self.buffer.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=position).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if let Some(event) = self.dispatch() {
+                    events.push(event);
+                }
+
+                continue;
+            }
+
+            // Comment line, usually used as a keep-alive.
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = line.split_once(':').map_or((line, ""), |(field, value)| {
+                (field, value.strip_prefix(' ').unwrap_or(value))
+            });
+
+            match field {
+                "event" => value.clone_into(&mut self.name),
+                "data" => {
+                    self.data.push_str(value);
+                    self.data.push('\n');
+                }
+                "id" => value.clone_into(&mut self.id),
+                _ => {}
+            }
+        }
+
+        events
+    }
+
+    fn dispatch(&mut self) -> Option<Event> {
+        let name = std::mem::take(&mut self.name);
+        let mut data = std::mem::take(&mut self.data);
+
+        if data.is_empty() {
+            return None;
+        }
+
+        data.pop();
+
+        Some(Event {
+            name: if name.is_empty() {
+                "message".to_string()
+            } else {
+                name
+            },
+            id: self.id.clone(),
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_multi_line_data() {
+        let events = EventSource::default().feed(b"event: PB_CONNECT\ndata: {\ndata: }\n\n");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "PB_CONNECT");
+        assert_eq!(events[0].data, "{\n}");
+    }
+
+    #[test]
+    fn handles_crlf_line_endings() {
+        let events = EventSource::default().feed(b"data: first\r\n\r\ndata: second\r\n\r\n");
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].name, "message");
+        assert_eq!(events[0].data, "first");
+        assert_eq!(events[1].data, "second");
+    }
+
+    #[test]
+    fn skips_comments() {
+        let events = EventSource::default().feed(b": keep-alive\n\n:\ndata: value\n\n");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "value");
+    }
+
+    #[test]
+    fn keeps_the_last_id_and_ignores_retry() {
+        let mut source = EventSource::default();
+
+        let events = source.feed(b"id: 1\nretry: 1000\ndata: a\n\ndata: b\n\n");
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id, "1");
+        assert_eq!(events[0].data, "a");
+        assert_eq!(events[1].id, "1");
+        assert_eq!(events[1].data, "b");
+    }
+
+    #[test]
+    fn completes_events_split_across_chunks() {
+        let mut source = EventSource::default();
+
+        assert!(source.feed(b"event: art").is_empty());
+        assert!(source.feed(b"icles/*\ndata: {\"re").is_empty());
+        // The two bytes of `é` are received separately.
+        assert!(source.feed(b"cord\": \"\xC3").is_empty());
+        assert!(source.feed(b"\xA9\"}\r").is_empty());
+
+        let events = source.feed(b"\n\r\n");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name, "articles/*");
+        assert_eq!(events[0].data, "{\"record\": \"é\"}");
+    }
+}
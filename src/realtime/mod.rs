@@ -0,0 +1,630 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+use tokio::sync::{mpsc, watch};
+
+use crate::error::{Error, ErrorKind};
+use crate::response::decode_response;
+use crate::PocketBase;
+use event_source::{Event, EventSource};
+
+mod event_source;
+pub mod subscribe;
+
+/// The realtime connection is a long-lived request, it must not be cut by the client's default timeout.
+///
+/// `PocketBase` closes idle realtime connections by itself after 5 minutes.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Considers the connection lost when nothing was received for longer than the
+/// idle timeout of `PocketBase` (5 minutes), to detect half-open connections.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(6 * 60);
+
+/// Delays between two reconnection attempts, the last one being repeated.
+///
+/// These are the same as the official JavaScript SDK.
+const RECONNECT_INTERVALS: [Duration; 7] = [
+    Duration::from_millis(200),
+    Duration::from_millis(300),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_millis(1200),
+    Duration::from_millis(1500),
+    Duration::from_secs(2),
+];
+
+/// Number of reconnections after which the connection is closed, when the subscriptions
+/// couldn't be submitted again after any of them.
+const MAX_RESUBSCRIPTION_ATTEMPTS: usize = 5;
+
+/// Represents the various errors that can be obtained while using the realtime API.
+#[derive(Error, Debug)]
+pub enum RealtimeError {
+    /// Communication with the `PocketBase` realtime API failed.
+    ///
+    /// This could be caused by an internet outage, an error in the link given to the `PocketBase` SDK
+    /// and similar errors.
+    #[error("The communication with the PocketBase realtime API failed: {0}")]
+    Unreachable(Error),
+    /// The realtime connection was closed before the action could be completed.
+    #[error("The realtime connection is closed.")]
+    Disconnected,
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
+    ///
+    /// One of the submitted subscriptions is invalid.
+    #[error("Bad Request: One of the submitted subscriptions is invalid. {}", .0.message())]
+    BadRequest(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
+    ///
+    /// The authorized record changed since the realtime connection was established.
+    #[error("The authorized record changed since the realtime connection was established.")]
+    Forbidden(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
+    ///
+    /// The realtime client id is unknown to the `PocketBase` instance.
+    #[error("The realtime client id is unknown to the PocketBase instance.")]
+    NotFound(Error),
+    /// An event could not be parsed into the expected data structure.
+    #[error("Could not parse realtime event into the expected data structure. It usually means that there is a mismatch between the provided Generic Type Parameter and your Collection definition: {0}")]
+    ParseError(Error),
+    /// The response from the `PocketBase` instance API was unexpected.
+    /// If you think its an error, please [open an issue on GitHub]("https://github.com/fromhorizons/pocketbase-rs/issues").
+    #[error("An unhandled status code was returned by the PocketBase API: {0}")]
+    UnexpectedResponse(Error),
+}
+
+impl From<Error> for RealtimeError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            ErrorKind::BadRequest => Self::BadRequest(error),
+            ErrorKind::Forbidden => Self::Forbidden(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::Unreachable => Self::Unreachable(error),
+            ErrorKind::ParseError => Self::ParseError(error),
+            _ => Self::UnexpectedResponse(error),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum ConnectionState {
+    Connecting,
+    Connected { client_id: String },
+    Reconnecting,
+    Closed { reason: Option<Error> },
+}
+
+/// Message sent by the connection task to the subscribers.
+enum Message {
+    Event(String),
+    Reconnected,
+    /// The connection is closed for the given reason, no message follows.
+    Closed(Error),
+}
+
+/// Why [`listen`] stopped listening to a realtime connection.
+enum ListenError {
+    /// The connection couldn't be established, or was lost.
+    Connection(RealtimeError),
+    /// The connection was re-established, but the subscriptions couldn't be submitted again.
+    Resubscription(RealtimeError),
+}
+
+impl From<RealtimeError> for ListenError {
+    fn from(error: RealtimeError) -> Self {
+        Self::Connection(error)
+    }
+}
+
+impl From<Error> for ListenError {
+    fn from(error: Error) -> Self {
+        Self::Connection(error.into())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConnectEvent {
+    client_id: String,
+}
+
+/// Subscribers of every submitted subscription, by subscription name.
+#[derive(Default)]
+struct Listeners {
+    next_id: u64,
+    topics: HashMap<String, Vec<(u64, mpsc::UnboundedSender<Message>)>>,
+}
+
+/// Holds the realtime connection of a [`PocketBase`] client, shared by its clones.
+///
+/// The connection is only referenced weakly, so that it is closed once the [`Realtime`]
+/// handles and the subscriptions using it are dropped.
+#[derive(Default)]
+pub struct RealtimeSlot {
+    connect_lock: tokio::sync::Mutex<()>,
+    current: Mutex<Weak<Connection>>,
+}
+
+impl RealtimeSlot {
+    fn current(&self) -> Option<Realtime> {
+        self.current
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .upgrade()
+            .map(|connection| Realtime { connection })
+            .filter(|realtime| !realtime.is_closed())
+    }
+}
+
+/// State shared between the [`Realtime`] handles, their subscriptions and the connection task.
+struct Shared {
+    base_url: String,
+    reqwest_client: reqwest::Client,
+    auth_token: Mutex<Option<String>>,
+    state: watch::Sender<ConnectionState>,
+    listeners: Mutex<Listeners>,
+    submit_lock: tokio::sync::Mutex<()>,
+    resubmit: mpsc::UnboundedSender<()>,
+}
+
+struct Connection {
+    shared: Arc<Shared>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A connection to the realtime API of a `PocketBase` instance.
+///
+/// A `Realtime` handle is obtained with [`PocketBase::realtime`], and is cheap to clone.
+/// Every clone, as well as every [`Subscription`](crate::Subscription), shares the same
+/// underlying Server-Sent Events connection, which is closed once all of them are dropped
+/// or when [`Realtime::disconnect`] is called.
+#[derive(Clone)]
+pub struct Realtime {
+    connection: Arc<Connection>,
+}
+
+impl std::fmt::Debug for Realtime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Realtime")
+            .field("client_id", &self.client_id())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PocketBase {
+    /// Returns the realtime connection of this client, connecting to the `PocketBase` instance if needed.
+    ///
+    /// The first call opens the `/api/realtime` Server-Sent Events connection and waits for the
+    /// `PB_CONNECT` event holding the realtime client id. Subsequent calls return the same connection,
+    /// as long as it is still open: the connection is closed once every [`Realtime`] handle and
+    /// [`Subscription`](crate::Subscription) using it is dropped.
+    ///
+    /// Once established, the connection is automatically re-opened with a backoff when it is lost
+    /// *(for example when the `PocketBase` instance restarts)*, as long as there are active subscriptions.
+    /// The subscriptions are then submitted again under the new client id, and each of them receives a
+    /// [`SubscriptionEvent::Reconnected`](crate::SubscriptionEvent::Reconnected) event.
+    /// If `PocketBase` rejects them, or they couldn't be submitted after several reconnections,
+    /// the connection is closed and each subscription yields the error before ending.
+    ///
+    /// You usually don't need to call this method yourself, [`Collection::subscribe`](crate::Collection::subscribe)
+    /// does it for you.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the server fails (`RealtimeError::Unreachable`).
+    /// - The server responds with an unexpected status (`RealtimeError::UnexpectedResponse`).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use pocketbase_rs::PocketBase;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     let realtime = pb.realtime().await?;
+    ///
+    ///     println!("Realtime client id: {:?}", realtime.client_id());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn realtime(&self) -> Result<Realtime, RealtimeError> {
+        let _guard = self.realtime.connect_lock.lock().await;
+
+        if let Some(realtime) = self.realtime.current() {
+            realtime.shared().set_auth_token(self.token());
+
+            return Ok(realtime);
+        }
+
+        let realtime = Realtime::connect(self).await?;
+
+        *self
+            .realtime
+            .current
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Arc::downgrade(&realtime.connection);
+
+        Ok(realtime)
+    }
+
+    /// Makes the realtime connection, if any, use the current auth token
+    /// the next time the subscriptions are submitted.
+    pub(crate) fn sync_realtime_auth_token(&self) {
+        if let Some(realtime) = self.realtime.current() {
+            realtime.shared().set_auth_token(self.token());
+        }
+    }
+}
+
+impl Realtime {
+    async fn connect(client: &PocketBase) -> Result<Self, RealtimeError> {
+        let (resubmit, resubmit_receiver) = mpsc::unbounded_channel();
+
+        let shared = Arc::new(Shared {
+            base_url: client.base_url(),
+            reqwest_client: client.reqwest_client.clone(),
+            auth_token: Mutex::new(client.token()),
+            state: watch::Sender::new(ConnectionState::Connecting),
+            listeners: Mutex::default(),
+            submit_lock: tokio::sync::Mutex::new(()),
+            resubmit,
+        });
+
+        let mut state = shared.state.subscribe();
+        let task = tokio::spawn(run(Arc::clone(&shared), resubmit_receiver));
+
+        let realtime = Self {
+            connection: Arc::new(Connection { shared, task }),
+        };
+
+        let state = state
+            .wait_for(|state| !matches!(state, ConnectionState::Connecting))
+            .await
+            .map_err(|_| RealtimeError::Disconnected)?
+            .clone();
+
+        match state {
+            ConnectionState::Closed {
+                reason: Some(reason),
+            } => Err(reason.into()),
+            ConnectionState::Closed { reason: None } => Err(RealtimeError::Disconnected),
+            _ => Ok(realtime),
+        }
+    }
+
+    fn shared(&self) -> &Arc<Shared> {
+        &self.connection.shared
+    }
+
+    /// Returns the realtime client id assigned by the `PocketBase` instance, if connected.
+    ///
+    /// The client id changes every time the connection is re-established.
+    #[must_use]
+    pub fn client_id(&self) -> Option<String> {
+        match &*self.shared().state.borrow() {
+            ConnectionState::Connected { client_id } => Some(client_id.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the realtime connection is closed.
+    ///
+    /// Once closed, the subscriptions stop receiving events.
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        matches!(
+            *self.shared().state.borrow(),
+            ConnectionState::Closed { .. }
+        )
+    }
+
+    /// Closes the realtime connection.
+    ///
+    /// Every [`Subscription`](crate::Subscription) using this connection ends.
+    pub fn disconnect(&self) {
+        self.connection.task.abort();
+        self.shared().close(None);
+    }
+}
+
+impl Shared {
+    fn listeners(&self) -> MutexGuard<'_, Listeners> {
+        self.listeners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn set_auth_token(&self, token: Option<String>) {
+        *self
+            .auth_token
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = token;
+    }
+
+    fn client_id(&self) -> Option<String> {
+        match &*self.state.borrow() {
+            ConnectionState::Connected { client_id } => Some(client_id.clone()),
+            _ => None,
+        }
+    }
+
+    /// Waits for the connection to be (re-)established, and returns its client id.
+    async fn connected_client_id(&self) -> Result<String, RealtimeError> {
+        let mut state = self.state.subscribe();
+
+        let state = state
+            .wait_for(|state| {
+                matches!(
+                    state,
+                    ConnectionState::Connected { .. } | ConnectionState::Closed { .. }
+                )
+            })
+            .await
+            .map_err(|_| RealtimeError::Disconnected)?;
+
+        match &*state {
+            ConnectionState::Connected { client_id } => Ok(client_id.clone()),
+            _ => Err(RealtimeError::Disconnected),
+        }
+    }
+
+    fn add_listener(&self, subscription: &str) -> (u64, mpsc::UnboundedReceiver<Message>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        let mut listeners = self.listeners();
+        let id = listeners.next_id;
+        listeners.next_id += 1;
+
+        listeners
+            .topics
+            .entry(subscription.to_string())
+            .or_default()
+            .push((id, sender));
+        drop(listeners);
+
+        (id, receiver)
+    }
+
+    fn remove_listener(&self, subscription: &str, id: u64) {
+        let mut listeners = self.listeners();
+
+        let Some(subscribers) = listeners.topics.get_mut(subscription) else {
+            return;
+        };
+
+        subscribers.retain(|(subscriber_id, _)| *subscriber_id != id);
+
+        if subscribers.is_empty() {
+            listeners.topics.remove(subscription);
+            drop(listeners);
+
+            // The connection task submits the new subscriptions set, `Drop` can't wait for it.
+            let _ = self.resubmit.send(());
+        }
+    }
+
+    fn dispatch(&self, event: &Event) {
+        if let Some(subscribers) = self.listeners().topics.get(&event.name) {
+            for (_, sender) in subscribers {
+                let _ = sender.send(Message::Event(event.data.clone()));
+            }
+        }
+    }
+
+    fn notify_reconnected(&self) {
+        for subscribers in self.listeners().topics.values() {
+            for (_, sender) in subscribers {
+                let _ = sender.send(Message::Reconnected);
+            }
+        }
+    }
+
+    fn has_listeners(&self) -> bool {
+        !self.listeners().topics.is_empty()
+    }
+
+    fn close(&self, reason: Option<Error>) {
+        let mut listeners = self.listeners();
+
+        if let Some(reason) = &reason {
+            for (_, sender) in listeners.topics.values().flatten() {
+                let _ = sender.send(Message::Closed(reason.clone()));
+            }
+        }
+
+        // Dropping the senders ends every subscription stream.
+        listeners.topics.clear();
+        drop(listeners);
+
+        self.state.send_replace(ConnectionState::Closed { reason });
+    }
+
+    /// Submits the current subscriptions set to the `PocketBase` instance, under the given client id.
+    async fn submit(&self, client_id: &str) -> Result<(), RealtimeError> {
+        let _guard = self.submit_lock.lock().await;
+
+        let subscriptions: Vec<String> = self.listeners().topics.keys().cloned().collect();
+        let auth_token = self
+            .auth_token
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+
+        let mut request = self
+            .reqwest_client
+            .post(format!("{}/api/realtime", self.base_url))
+            .json(&json!({
+                "clientId": client_id,
+                "subscriptions": subscriptions,
+            }));
+
+        if let Some(token) = auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        let started_at = Instant::now();
+
+        let response = request.send().await;
+
+        #[cfg(feature = "tracing")]
+        crate::trace::record_response(&reqwest::Method::POST, &response, started_at.elapsed());
+
+        decode_response::<IgnoredAny>(response, started_at).await?;
+
+        Ok(())
+    }
+}
+
+/// Runs the realtime connection until it is closed.
+///
+/// Once the first connection succeeded, a lost connection is re-established for as
+/// long as there are subscriptions. The connection is closed, and the subscribers are
+/// given the reason, if `PocketBase` rejects the subscriptions submitted again after a
+/// reconnection, or after [`MAX_RESUBSCRIPTION_ATTEMPTS`] failed submissions in a row.
+async fn run(shared: Arc<Shared>, mut resubmit: mpsc::UnboundedReceiver<()>) {
+    let mut has_connected = false;
+    let mut attempt = 0;
+    let mut failed_resubscriptions = 0;
+
+    loop {
+        let mut connected = false;
+        let result = listen(&shared, &mut resubmit, has_connected, &mut connected).await;
+
+        if connected {
+            has_connected = true;
+            attempt = 0;
+            failed_resubscriptions = 0;
+        }
+
+        let error = match result {
+            Ok(()) => None,
+            Err(ListenError::Connection(error)) => Some(Error::from(error)),
+            Err(ListenError::Resubscription(error)) => {
+                let error = Error::from(error);
+                failed_resubscriptions += 1;
+
+                let rejected = error
+                    .status()
+                    .is_some_and(|status| status.is_client_error());
+
+                if rejected || failed_resubscriptions >= MAX_RESUBSCRIPTION_ATTEMPTS {
+                    shared.close(Some(error));
+                    return;
+                }
+
+                Some(error)
+            }
+        };
+
+        // A failed first connection is reported by `PocketBase::realtime`, and without subscriptions
+        // the next call to `PocketBase::realtime` simply opens a new connection.
+        if !has_connected || !shared.has_listeners() {
+            shared.close(error);
+            return;
+        }
+
+        shared.state.send_replace(ConnectionState::Reconnecting);
+
+        tokio::time::sleep(RECONNECT_INTERVALS[attempt.min(RECONNECT_INTERVALS.len() - 1)]).await;
+        attempt += 1;
+    }
+}
+
+/// Listens to a single realtime connection until it is lost.
+///
+/// When `reconnecting`, the subscriptions are submitted again as soon as the new
+/// client id is received, and the subscribers are notified.
+async fn listen(
+    shared: &Shared,
+    resubmit: &mut mpsc::UnboundedReceiver<()>,
+    reconnecting: bool,
+    connected: &mut bool,
+) -> Result<(), ListenError> {
+    let response = shared
+        .reqwest_client
+        .get(format!("{}/api/realtime", shared.base_url))
+        .header("Accept", "text/event-stream")
+        .timeout(CONNECTION_TIMEOUT)
+        .send()
+        .await
+        .map_err(Error::from)?;
+
+    if !response.status().is_success() {
+        return Err(Error::from_response(response).await.into());
+    }
+
+    let mut body = response.bytes_stream();
+    let mut event_source = EventSource::default();
+
+    loop {
+        tokio::select! {
+            chunk = tokio::time::timeout(IDLE_TIMEOUT, body.next()) => {
+                let chunk = chunk.map_err(|_| {
+                    Error::new(ErrorKind::Unreachable, "No data received from the realtime connection.")
+                })?;
+
+                let Some(chunk) = chunk else {
+                    return Ok(());
+                };
+
+                let chunk = chunk.map_err(Error::from)?;
+
+                for event in event_source.feed(&chunk) {
+                    if event.name != "PB_CONNECT" {
+                        shared.dispatch(&event);
+                        continue;
+                    }
+
+                    let client_id = serde_json::from_str::<ConnectEvent>(&event.data)
+                        .map_or(event.id, |connect| connect.client_id);
+
+                    shared.state.send_replace(ConnectionState::Connected {
+                        client_id: client_id.clone(),
+                    });
+
+                    if reconnecting {
+                        shared
+                            .submit(&client_id)
+                            .await
+                            .map_err(ListenError::Resubscription)?;
+                        shared.notify_reconnected();
+                    }
+
+                    *connected = true;
+                }
+            }
+
+            Some(()) = resubmit.recv() => {
+                while resubmit.try_recv().is_ok() {}
+
+                // Before `PB_CONNECT`, the subscriptions are submitted once connected.
+                // Best effort: on failure the instance keeps sending events for topics
+                // nobody listens to anymore, which are simply ignored.
+                if let Some(client_id) = shared.client_id() {
+                    let _ = shared.submit(&client_id).await;
+                }
+            }
+        }
+    }
+}
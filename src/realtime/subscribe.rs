@@ -0,0 +1,340 @@
+use std::borrow::Cow;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use serde::{de::DeserializeOwned, Deserialize};
+use tokio::sync::mpsc;
+
+use super::{Message, Realtime, RealtimeError};
+use crate::error::{Error, ErrorKind};
+use crate::{Collection, PocketBase};
+
+/// The action that triggered a [`RecordEvent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordAction {
+    /// A record was created.
+    Create,
+    /// A record was updated.
+    Update,
+    /// A record was deleted.
+    Delete,
+}
+
+/// An event received by a [`Subscription`].
+#[derive(Clone, Debug)]
+pub enum SubscriptionEvent<T> {
+    /// A record of the subscribed topic changed.
+    Record(RecordEvent<T>),
+    /// The realtime connection was lost and has been re-established.
+    ///
+    /// Changes that happened while disconnected were not received,
+    /// you may want to re-synchronize any state built from the previous events.
+    Reconnected,
+}
+
+/// A change of a record, received from the realtime API.
+#[derive(Clone, Debug, Deserialize)]
+pub struct RecordEvent<T> {
+    /// The action that triggered the event.
+    pub action: RecordAction,
+    /// The record, as it is after the action *(or before, for a deletion)*.
+    pub record: T,
+}
+
+pub struct CollectionSubscribeBuilder<'a, T: DeserializeOwned> {
+    client: &'a PocketBase,
+    collection_name: Cow<'a, str>,
+    topic: &'a str,
+    filter: Option<&'a str>,
+    expand: Option<&'a str>,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<'a> Collection<'a> {
+    /// Subscribe to the changes of the records of the given collection.
+    ///
+    /// The `topic` is either `*` to receive the changes of every record of the collection,
+    /// or the id of a single record.
+    ///
+    /// This function returns a `CollectionSubscribeBuilder`, which allows you to specify
+    /// additional options such as filtering or expanding linked records before calling
+    /// `.call().await` to subscribe.
+    ///
+    /// The returned [`Subscription`] is a [`Stream`] of [`SubscriptionEvent`], usually a [`RecordEvent`].
+    /// The topic is unsubscribed when the `Subscription` is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use futures::StreamExt;
+    /// use pocketbase_rs::{PocketBase, RecordAction, SubscriptionEvent};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Clone, Debug)]
+    /// struct Article {
+    ///     id: String,
+    ///     title: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     // ...
+    ///
+    ///     let mut articles = pb
+    ///         .collection("articles")?
+    ///         .subscribe::<Article>("*")
+    ///         .filter("language='en'")
+    ///         .call()
+    ///         .await?;
+    ///
+    ///     while let Some(event) = articles.next().await {
+    ///         match event? {
+    ///             SubscriptionEvent::Record(event) if event.action == RecordAction::Create => {
+    ///                 println!("New article: {}", event.record.title);
+    ///             }
+    ///             SubscriptionEvent::Reconnected => println!("Some changes may have been missed."),
+    ///             _ => {}
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn subscribe<T: DeserializeOwned>(
+        self,
+        topic: &'a str,
+    ) -> CollectionSubscribeBuilder<'a, T> {
+        CollectionSubscribeBuilder {
+            client: self.client,
+            collection_name: self.name,
+            topic,
+            filter: None,
+            expand: None,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, T: DeserializeOwned> CollectionSubscribeBuilder<'a, T> {
+    /// Only receive the events of the records matching the given filter.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?filter=(status="published" && created>'1970-01-01')
+    /// ```
+    ///
+    /// Uses the same syntax as the `filter` of the records list.
+    pub const fn filter(mut self, filter: &'a str) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Auto expand the relations of the received records.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?expand=relField1,relField2.subRelField
+    /// ```
+    ///
+    /// Supports up to 6-levels depth nested relations expansion.
+    /// Only the relations to which the subscribed user has permissions to **view** will be expanded.
+    pub const fn expand(mut self, expand: &'a str) -> Self {
+        self.expand = Some(expand);
+        self
+    }
+
+    /// The subscription name submitted to `PocketBase`, which is also the name of the received events.
+    ///
+    /// Options are appended as `?options={"query":{...}}`, URL encoded.
+    fn subscription_name(&self) -> String {
+        let topic = format!("{}/{}", self.collection_name, self.topic);
+
+        let mut query = serde_json::Map::new();
+
+        if let Some(filter) = self.filter {
+            query.insert("filter".to_string(), filter.into());
+        }
+
+        if let Some(expand) = self.expand {
+            query.insert("expand".to_string(), expand.into());
+        }
+
+        if query.is_empty() {
+            return topic;
+        }
+
+        let options = serde_json::json!({ "query": query }).to_string();
+        let options: String = url::form_urlencoded::byte_serialize(options.as_bytes()).collect();
+
+        format!("{topic}?options={options}")
+    }
+
+    /// Subscribes to the topic and returns the stream of its events.
+    ///
+    /// This method connects to the realtime API if needed, and submits the
+    /// subscriptions to the `PocketBase` instance.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the server fails (`RealtimeError::Unreachable`).
+    /// - The realtime connection was closed meanwhile (`RealtimeError::Disconnected`).
+    /// - The subscription is invalid (`RealtimeError::BadRequest`).
+    /// - The authenticated record changed since the connection was established (`RealtimeError::Forbidden`).
+    /// - The server responds with an unexpected status (`RealtimeError::UnexpectedResponse`).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "subscribe",
+            skip_all,
+            fields(
+                collection = %self.collection_name,
+                topic = %self.topic,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn call(self) -> Result<Subscription<T>, RealtimeError> {
+        let realtime = self.client.realtime().await?;
+        let name = self.subscription_name();
+        let (listener_id, receiver) = realtime.shared().add_listener(&name);
+
+        let subscription = Subscription {
+            realtime,
+            name,
+            listener_id,
+            receiver,
+            _marker: std::marker::PhantomData,
+        };
+
+        // On failure, dropping the subscription removes its listener.
+        let shared = subscription.realtime.shared();
+        let client_id = shared.connected_client_id().await?;
+        shared.submit(&client_id).await?;
+
+        Ok(subscription)
+    }
+}
+
+/// A [`Stream`] of the [`SubscriptionEvent`]s of a realtime topic.
+///
+/// Created by [`Collection::subscribe`]. The topic is unsubscribed when this struct is dropped.
+/// The stream ends when the realtime connection is closed, lost connections being
+/// re-established automatically.
+///
+/// When the subscriptions can't be submitted again after a reconnection *(for example because
+/// `PocketBase` rejects them with a 4xx error)*, the connection is closed and the stream yields
+/// the reason as its last item.
+pub struct Subscription<T> {
+    realtime: Realtime,
+    name: String,
+    listener_id: u64,
+    receiver: mpsc::UnboundedReceiver<Message>,
+    _marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> Subscription<T> {
+    /// Returns the subscription name, as submitted to `PocketBase` *(example: `articles/*`)*.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the realtime connection used by this subscription.
+    #[must_use]
+    pub const fn realtime(&self) -> &Realtime {
+        &self.realtime
+    }
+}
+
+impl<T> std::fmt::Debug for Subscription<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: DeserializeOwned> Stream for Subscription<T> {
+    type Item = Result<SubscriptionEvent<T>, RealtimeError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx).map(|message| {
+            message.map(|message| match message {
+                Message::Event(data) => serde_json::from_str::<RecordEvent<T>>(&data)
+                    .map(SubscriptionEvent::Record)
+                    .map_err(|error| {
+                        RealtimeError::ParseError(Error::new(
+                            ErrorKind::ParseError,
+                            error.to_string(),
+                        ))
+                    }),
+                Message::Reconnected => Ok(SubscriptionEvent::Reconnected),
+                Message::Closed(reason) => Err(reason.into()),
+            })
+        })
+    }
+}
+
+impl<T> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        self.realtime
+            .shared()
+            .remove_listener(&self.name, self.listener_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    #[test]
+    fn names_subscriptions_without_options_after_their_topic() {
+        let pb = PocketBase::new("http://localhost:8090");
+        let articles = pb.collection("articles").unwrap().subscribe::<Value>("*");
+
+        assert_eq!(articles.subscription_name(), "articles/*");
+    }
+
+    #[test]
+    fn url_encodes_the_filter_and_expand_options() {
+        let pb = PocketBase::new("http://localhost:8090");
+        let articles = pb
+            .collection("articles")
+            .unwrap()
+            .subscribe::<Value>("*")
+            .filter(r#"status = "published" && views > 10"#)
+            .expand("author");
+
+        assert_eq!(
+            articles.subscription_name(),
+            "articles/*?options=%7B%22query%22%3A%7B%22expand%22%3A%22author%22%2C%22filter%22%3A\
+             %22status+%3D+%5C%22published%5C%22+%26%26+views+%3E+10%22%7D%7D"
+        );
+    }
+
+    #[test]
+    fn keeps_only_the_given_options() {
+        let pb = PocketBase::new("http://localhost:8090");
+        let article = pb
+            .collection("articles")
+            .unwrap()
+            .subscribe::<Value>("a1")
+            .expand("author,tags");
+
+        assert_eq!(
+            article.subscription_name(),
+            "articles/a1?options=%7B%22query%22%3A%7B%22expand%22%3A%22author%2Ctags%22%7D%7D"
+        );
+    }
+}
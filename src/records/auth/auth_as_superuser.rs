@@ -0,0 +1,54 @@
+use std::borrow::Cow;
+
+use super::SUPERUSERS_COLLECTION;
+use crate::error::AuthenticationError;
+use crate::{AuthStore, Collection, PocketBase};
+
+impl PocketBase {
+    /// Authenticates the client as a superuser, with their email and password.
+    ///
+    /// This is a shortcut for password authentication against the `_superusers` system collection.
+    /// Superusers bypass every collection API rule, and are required by admin-only APIs
+    /// such as [`crate::Collection::impersonate`].
+    ///
+    /// Upon successful authentication, the client's internal auth store is updated with the
+    /// authentication token and superuser information, which will be automatically included in
+    /// subsequent API requests. [`AuthStore::is_superuser`] then returns `true`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as [`crate::Collection::auth_with_password`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use pocketbase_rs::PocketBase;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     let auth_data = pb
+    ///         .auth_as_superuser("admin@domain.com", "secure-password")
+    ///         .await?;
+    ///
+    ///     assert!(auth_data.is_superuser());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn auth_as_superuser(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> Result<AuthStore, AuthenticationError> {
+        Collection {
+            client: self,
+            name: Cow::Borrowed(SUPERUSERS_COLLECTION),
+        }
+        .auth_with_password(email, password)
+        .await
+    }
+}
@@ -13,7 +13,11 @@ impl Collection<'_> {
     /// - `RequestError::Unauthorized` if the provided token is invalid.
     /// - `RequestError::Forbidden` if the operation is not permitted.
     /// - `RequestError::NotFound` if the target user or session cannot be located.
-    /// - `RequestError::Unhandled` for all other error cases.
+    /// - `RequestError::BadRequest` if the request was refused, with the message of `PocketBase`.
+    /// - `RequestError::TooManyRequests` if the request was rate limited.
+    /// - `RequestError::Unreachable` if the request to the server fails.
+    /// - `RequestError::ParseError` if the response could not be parsed into the expected data structure.
+    /// - `RequestError::Unhandled` for all other error cases, including server errors.
     ///
     /// # Example
     ///
@@ -24,16 +28,16 @@ impl Collection<'_> {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///    let mut pb = PocketBase::new("http://localhost:8090");
+    ///    let pb = PocketBase::new("http://localhost:8090");
     ///
     ///    let auth_data = pb
-    ///        .collection("_superusers")
+    ///        .collection("_superusers")?
     ///        .auth_with_password("test@test.com", "abcdefghijkl")
     ///        .await?;
     ///
     ///    println!("pre auth data: {auth_data:?}");
     ///
-    ///    let auth_data = pb.collection("_superusers").auth_refresh().await?;
+    ///    let auth_data = pb.collection("_superusers")?.auth_refresh().await?;
     ///
     ///    println!("post auth data: {auth_data:?}");
     ///
@@ -41,34 +45,32 @@ impl Collection<'_> {
     ///}
     ///
     /// ```
-    pub async fn auth_refresh(&mut self) -> Result<AuthStore, RequestError> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "auth_refresh",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn auth_refresh(&self) -> Result<AuthStore, RequestError> {
         let url = format!(
             "{}/api/collections/{}/auth-refresh",
             self.client.base_url(),
             self.name
         );
 
-        let request = self.client.request_post(&url).send().await;
+        let auth_store = self
+            .client
+            .send_and_decode_without_refresh::<AuthStore>(self.client.request_post(&url))
+            .await?;
 
-        match request {
-            Ok(response) => match response.status() {
-                reqwest::StatusCode::OK => {
-                    let Ok(auth_store) = response.json::<AuthStore>().await else {
-                        return Err(RequestError::Unhandled);
-                    };
+        self.client.update_auth_store(auth_store.clone());
 
-                    self.client.update_auth_store(auth_store.clone());
-
-                    Ok(auth_store)
-                }
-
-                reqwest::StatusCode::UNAUTHORIZED => Err(RequestError::Unauthorized),
-                reqwest::StatusCode::FORBIDDEN => Err(RequestError::Forbidden),
-                reqwest::StatusCode::NOT_FOUND => Err(RequestError::NotFound),
-
-                _ => Err(RequestError::Unhandled),
-            },
-            Err(_) => Err(RequestError::Unhandled),
-        }
+        Ok(auth_store)
     }
 }
@@ -20,7 +20,11 @@ impl<'a> Collection<'a> {
     /// - `RequestError::Unauthorized` if the provided user token is invalid.
     /// - `RequestError::Forbidden` if the operation is not permitted for the provided user.
     /// - `RequestError::NotFound` if the target user or session cannot be located.
-    /// - `RequestError::Unhandled` for all other error cases.
+    /// - `RequestError::BadRequest` if the request was refused, with the message of `PocketBase`.
+    /// - `RequestError::TooManyRequests` if the request was rate limited.
+    /// - `RequestError::Unreachable` if the request to the server fails.
+    /// - `RequestError::ParseError` if the response could not be parsed into the expected data structure.
+    /// - `RequestError::Unhandled` for all other error cases, including server errors.
     ///
     /// # Example
     ///
@@ -31,12 +35,12 @@ impl<'a> Collection<'a> {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///    let mut pb = PocketBase::new("http://localhost:8090");
+    ///    let pb = PocketBase::new("http://localhost:8090");
     ///
     ///    // ...
     ///
     ///    let auth_data = pb
-    ///        .collection("users")
+    ///        .collection("users")?
     ///        .auth_refresh_for_user("USER_TOKEN")
     ///        .await?;
     ///
@@ -46,8 +50,20 @@ impl<'a> Collection<'a> {
     ///}
     ///
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "auth_refresh",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn auth_refresh_for_user(
-        &mut self,
+        &self,
         user_token: &'a str,
     ) -> Result<AuthStore, RequestError> {
         let url = format!(
@@ -65,27 +81,13 @@ impl<'a> Collection<'a> {
             .client
             .reqwest_client
             .post(&url)
-            .bearer_auth(user_token)
-            .send()
-            .await;
+            .bearer_auth(user_token);
 
-        match request {
-            Ok(response) => match response.status() {
-                reqwest::StatusCode::OK => {
-                    let Ok(auth_store) = response.json::<AuthStore>().await else {
-                        return Err(RequestError::Unhandled);
-                    };
-
-                    Ok(auth_store)
-                }
-
-                reqwest::StatusCode::UNAUTHORIZED => Err(RequestError::Unauthorized),
-                reqwest::StatusCode::FORBIDDEN => Err(RequestError::Forbidden),
-                reqwest::StatusCode::NOT_FOUND => Err(RequestError::NotFound),
+        let auth_store = self
+            .client
+            .send_and_decode_without_refresh::<AuthStore>(request)
+            .await?;
 
-                _ => Err(RequestError::Unhandled),
-            },
-            Err(_) => Err(RequestError::Unhandled),
-        }
+        Ok(auth_store)
     }
 }
@@ -0,0 +1,158 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use tokio::sync::watch;
+
+use super::auth_store_backend::{AuthStoreBackend, AuthStoreBackendError};
+use super::AuthStore;
+
+/// The authentication session of a [`crate::PocketBase`] client, shared by its clones.
+///
+/// The auth store sits behind a [`watch`] channel so that it can be replaced by the automatic
+/// token refresh, which only has a shared reference to the client, and so that changes can be
+/// observed with [`crate::PocketBase::auth_store_changes`].
+pub struct AuthState {
+    auth_store: watch::Sender<Option<AuthStore>>,
+    /// Whether the token can be renewed with `auth-refresh`.
+    /// Impersonation tokens can't.
+    ///
+    /// Only written while the `auth_store` channel is locked by `send_modify`, and read while
+    /// it is borrowed, so that a session never pairs a token with the flag of another one.
+    refreshable: AtomicBool,
+    backend: RwLock<Option<Arc<dyn AuthStoreBackend>>>,
+    /// Called with the errors of the backend while saving or clearing the auth store.
+    error_handler: RwLock<Option<BackendErrorHandler>>,
+    /// Held while refreshing the token, so that concurrent requests only refresh it once.
+    pub(super) refresh_lock: tokio::sync::Mutex<()>,
+}
+
+/// A callback receiving the errors of an [`AuthStoreBackend`].
+pub type BackendErrorHandler = Arc<dyn Fn(&AuthStoreBackendError) + Send + Sync>;
+
+#[derive(Clone, Debug)]
+pub(super) struct Session {
+    pub(super) auth_store: AuthStore,
+    pub(super) refreshable: bool,
+}
+
+impl AuthState {
+    pub fn auth_store(&self) -> Option<AuthStore> {
+        self.auth_store.borrow().clone()
+    }
+
+    pub fn token(&self) -> Option<String> {
+        self.auth_store
+            .borrow()
+            .as_ref()
+            .map(|auth_store| auth_store.token.clone())
+    }
+
+    /// Replaces the auth store, and writes it to the backend.
+    ///
+    /// Errors of the backend don't fail the login: the session stays usable in memory, and the
+    /// error is reported, see [`AuthState::report_backend_error`].
+    pub fn set(&self, auth_store: AuthStore, refreshable: bool) {
+        if let Some(backend) = self.backend() {
+            self.report_backend_error("saved to", backend.save(&auth_store));
+        }
+
+        self.replace(Some(auth_store), refreshable);
+    }
+
+    /// Removes the auth store, and clears the backend.
+    ///
+    /// Errors of the backend are reported, see [`AuthState::report_backend_error`].
+    pub fn clear(&self) {
+        if let Some(backend) = self.backend() {
+            self.report_backend_error("cleared from", backend.clear());
+        }
+
+        self.replace(None, true);
+    }
+
+    /// Replaces the auth store and its `refreshable` flag at once.
+    fn replace(&self, auth_store: Option<AuthStore>, refreshable: bool) {
+        self.auth_store.send_modify(|current| {
+            self.refreshable.store(refreshable, Ordering::Release);
+            *current = auth_store;
+        });
+    }
+
+    /// Attaches a backend, replacing the auth store with the one it persisted, if any.
+    pub fn set_backend(
+        &self,
+        backend: Arc<dyn AuthStoreBackend>,
+    ) -> Result<(), AuthStoreBackendError> {
+        if let Some(auth_store) = backend.load()? {
+            self.replace(Some(auth_store), true);
+        }
+
+        *self.backend.write().unwrap_or_else(PoisonError::into_inner) = Some(backend);
+
+        Ok(())
+    }
+
+    fn backend(&self) -> Option<Arc<dyn AuthStoreBackend>> {
+        self.backend
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Sets the callback receiving the errors of the backend while saving or clearing.
+    pub fn set_error_handler(&self, handler: BackendErrorHandler) {
+        *self
+            .error_handler
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(handler);
+    }
+
+    /// Reports an error of the backend to the error handler, and as a `WARN` event with the
+    /// `tracing` feature. It doesn't prevent the session from being used in memory.
+    fn report_backend_error(&self, operation: &str, result: Result<(), AuthStoreBackendError>) {
+        let Err(error) = result else {
+            return;
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(%error, "The auth store couldn't be {operation} its backend.");
+
+        #[cfg(not(feature = "tracing"))]
+        let _ = operation;
+
+        let handler = self
+            .error_handler
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+
+        if let Some(handler) = handler {
+            handler(&error);
+        }
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<Option<AuthStore>> {
+        self.auth_store.subscribe()
+    }
+
+    pub(super) fn session(&self) -> Option<Session> {
+        let auth_store = self.auth_store.borrow();
+
+        Some(Session {
+            auth_store: auth_store.clone()?,
+            refreshable: self.refreshable.load(Ordering::Acquire),
+        })
+    }
+}
+
+impl Default for AuthState {
+    fn default() -> Self {
+        Self {
+            auth_store: watch::Sender::new(None),
+            refreshable: AtomicBool::new(true),
+            backend: RwLock::default(),
+            error_handler: RwLock::default(),
+            refresh_lock: tokio::sync::Mutex::default(),
+        }
+    }
+}
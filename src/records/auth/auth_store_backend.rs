@@ -0,0 +1,323 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use thiserror::Error;
+
+use super::AuthStore;
+
+/// Represents the errors of an [`AuthStoreBackend`].
+#[derive(Error, Debug)]
+pub enum AuthStoreBackendError {
+    /// The auth store couldn't be read from, or written to, its storage.
+    #[error("The auth store couldn't be accessed: {0}")]
+    Io(#[from] std::io::Error),
+    /// The persisted auth store couldn't be serialized or deserialized.
+    #[error("The persisted auth store is invalid: {0}")]
+    Serialization(#[from] serde_json::Error),
+    /// An error of a custom backend.
+    #[error("The auth store backend failed: {0}")]
+    Other(String),
+}
+
+/// A storage for the auth store of a [`crate::PocketBase`] client, used to keep the
+/// session across restarts.
+///
+/// Once attached with [`crate::PocketBase::set_auth_store_backend`], the backend is written to
+/// on every login and token refresh, and cleared on [`crate::PocketBase::logout`].
+///
+/// This crate provides [`MemoryAuthStore`] and [`FileAuthStore`].
+pub trait AuthStoreBackend: Send + Sync {
+    /// Returns the persisted auth store, or `None` if there is no session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AuthStoreBackendError`] if the storage couldn't be read.
+    fn load(&self) -> Result<Option<AuthStore>, AuthStoreBackendError>;
+
+    /// Persists the given auth store, replacing the previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AuthStoreBackendError`] if the storage couldn't be written.
+    fn save(&self, auth_store: &AuthStore) -> Result<(), AuthStoreBackendError>;
+
+    /// Removes the persisted auth store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AuthStoreBackendError`] if the storage couldn't be written.
+    fn clear(&self) -> Result<(), AuthStoreBackendError>;
+}
+
+impl<T: AuthStoreBackend + ?Sized> AuthStoreBackend for Arc<T> {
+    fn load(&self) -> Result<Option<AuthStore>, AuthStoreBackendError> {
+        (**self).load()
+    }
+
+    fn save(&self, auth_store: &AuthStore) -> Result<(), AuthStoreBackendError> {
+        (**self).save(auth_store)
+    }
+
+    fn clear(&self) -> Result<(), AuthStoreBackendError> {
+        (**self).clear()
+    }
+}
+
+/// An [`AuthStoreBackend`] keeping the auth store in memory.
+///
+/// The session is lost when the process exits, but can be shared between clients
+/// through an [`Arc`].
+#[derive(Debug, Default)]
+pub struct MemoryAuthStore {
+    auth_store: Mutex<Option<AuthStore>>,
+}
+
+impl MemoryAuthStore {
+    /// Creates an empty in-memory auth store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl AuthStoreBackend for MemoryAuthStore {
+    fn load(&self) -> Result<Option<AuthStore>, AuthStoreBackendError> {
+        Ok(self
+            .auth_store
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone())
+    }
+
+    fn save(&self, auth_store: &AuthStore) -> Result<(), AuthStoreBackendError> {
+        *self
+            .auth_store
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(auth_store.clone());
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), AuthStoreBackendError> {
+        *self
+            .auth_store
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+
+        Ok(())
+    }
+}
+
+/// An [`AuthStoreBackend`] persisting the auth store in a JSON file.
+///
+/// The file is replaced atomically on every save. On Unix, it is only readable by its owner,
+/// as it contains the auth token.
+#[derive(Debug, Clone)]
+pub struct FileAuthStore {
+    path: PathBuf,
+}
+
+impl FileAuthStore {
+    /// Creates a backend persisting the auth store at the given path.
+    ///
+    /// The file is created on the first login, its parent directory must exist.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the path of the JSON file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AuthStoreBackend for FileAuthStore {
+    fn load(&self) -> Result<Option<AuthStore>, AuthStoreBackendError> {
+        match std::fs::read(&self.path) {
+            Ok(content) => Ok(Some(serde_json::from_slice(&content)?)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn save(&self, auth_store: &AuthStore) -> Result<(), AuthStoreBackendError> {
+        let content = serde_json::to_vec_pretty(auth_store)?;
+
+        let mut temporary_path = self.path.clone().into_os_string();
+        temporary_path.push(".tmp");
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(&temporary_path)?;
+
+        // The mode only applies to a new file, not to one left by an interrupted save.
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+
+        file.write_all(&content)?;
+        file.sync_all()?;
+
+        std::fs::rename(&temporary_path, &self.path)?;
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), AuthStoreBackendError> {
+        match std::fs::remove_file(&self.path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::PocketBase;
+
+    fn auth_store(token: &str) -> AuthStore {
+        serde_json::from_value(json!({
+            "token": token,
+            "record": {
+                "id": "u1",
+                "collectionId": "_pb_users_auth_",
+                "collectionName": "users",
+                "created": "2024-01-31 12:00:00.000Z",
+                "updated": "2024-01-31 12:00:00.000Z",
+                "email": "fox@domain.com",
+                "emailVisibility": false,
+                "verified": true,
+            },
+        }))
+        .unwrap()
+    }
+
+    /// A directory removed when dropped.
+    struct TemporaryDirectory(PathBuf);
+
+    impl TemporaryDirectory {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("pocketbase-rs-{name}-{}", std::process::id()));
+            std::fs::create_dir_all(&path).unwrap();
+
+            Self(path)
+        }
+    }
+
+    impl Drop for TemporaryDirectory {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn memory_store_round_trip() {
+        let store = MemoryAuthStore::new();
+        assert!(store.load().unwrap().is_none());
+
+        store.save(&auth_store("first")).unwrap();
+        store.save(&auth_store("second")).unwrap();
+        assert_eq!(store.load().unwrap().unwrap().token, "second");
+
+        store.clear().unwrap();
+        assert!(store.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn file_store_round_trip() {
+        let directory = TemporaryDirectory::new("round-trip");
+        let store = FileAuthStore::new(directory.0.join("session.json"));
+
+        store.save(&auth_store("first")).unwrap();
+        store.save(&auth_store("second")).unwrap();
+
+        let loaded = FileAuthStore::new(store.path()).load().unwrap().unwrap();
+        assert_eq!(loaded.token, "second");
+        assert_eq!(loaded.record.email, "fox@domain.com");
+
+        store.clear().unwrap();
+        assert!(store.load().unwrap().is_none());
+        assert!(!store.path().exists());
+
+        // Clearing twice isn't an error.
+        store.clear().unwrap();
+    }
+
+    #[test]
+    fn file_store_without_file_is_empty() {
+        let directory = TemporaryDirectory::new("missing");
+        let store = FileAuthStore::new(directory.0.join("session.json"));
+
+        assert!(store.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn file_store_replaces_the_file_through_a_temporary_one() {
+        let directory = TemporaryDirectory::new("rename");
+        let path = directory.0.join("session.json");
+        let temporary_path = directory.0.join("session.json.tmp");
+
+        // A temporary file left by an interrupted save is overwritten.
+        std::fs::write(&temporary_path, "interrupted").unwrap();
+
+        let store = FileAuthStore::new(&path);
+        store.save(&auth_store("token")).unwrap();
+
+        assert!(!temporary_path.exists());
+        assert_eq!(store.load().unwrap().unwrap().token, "token");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn file_store_reports_invalid_content() {
+        let directory = TemporaryDirectory::new("invalid");
+        let path = directory.0.join("session.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        assert!(matches!(
+            FileAuthStore::new(path).load(),
+            Err(AuthStoreBackendError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn reports_save_errors_to_the_handler() {
+        let directory = TemporaryDirectory::new("handler");
+        let errors = Arc::new(AtomicUsize::new(0));
+
+        let pb = PocketBase::new("http://localhost:8090");
+        pb.set_auth_store_backend(FileAuthStore::new(directory.0.join("missing/session.json")))
+            .unwrap();
+        pb.on_auth_store_backend_error({
+            let errors = errors.clone();
+            move |error| {
+                assert!(matches!(error, AuthStoreBackendError::Io(_)));
+                errors.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        pb.update_auth_store(auth_store("token"));
+
+        // The session stays usable in memory.
+        assert_eq!(pb.token().as_deref(), Some("token"));
+        assert_eq!(errors.load(Ordering::SeqCst), 1);
+    }
+}
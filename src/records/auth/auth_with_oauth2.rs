@@ -0,0 +1,232 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AuthenticationError, ErrorKind};
+use crate::{AuthStore, Collection, PocketBase};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OAuth2Credentials<'a, D> {
+    provider: &'a str,
+    code: &'a str,
+    code_verifier: &'a str,
+    #[serde(rename = "redirectURL")]
+    redirect_url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    create_data: Option<D>,
+}
+
+/// The response of a successful `OAuth2` authentication.
+///
+/// Returned by [`CollectionAuthWithOAuth2Builder::call`].
+#[derive(Clone, Debug, Deserialize)]
+pub struct OAuth2AuthResponse {
+    /// The authentication token and the authenticated record,
+    /// also stored in the client's auth store.
+    #[serde(flatten)]
+    pub auth_store: AuthStore,
+    /// The user information returned by the `OAuth2` provider.
+    pub meta: OAuth2Meta,
+}
+
+/// The user information returned by an `OAuth2` provider.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OAuth2Meta {
+    /// The id of the user for the provider.
+    pub id: String,
+    /// The name of the user.
+    pub name: String,
+    /// The username of the user.
+    pub username: String,
+    /// The email address of the user.
+    pub email: String,
+    /// The URL of the avatar of the user.
+    #[serde(rename = "avatarURL")]
+    pub avatar_url: String,
+    /// The `OAuth2` access token.
+    pub access_token: String,
+    /// The `OAuth2` refresh token.
+    pub refresh_token: String,
+    /// The expiration date of the access token.
+    pub expiry: String,
+    /// Whether a new auth record was created for this user.
+    pub is_new: bool,
+    /// The raw user data returned by the provider.
+    pub raw_user: serde_json::Map<String, serde_json::Value>,
+}
+
+pub struct CollectionAuthWithOAuth2Builder<'a, D: Serialize = ()> {
+    client: &'a PocketBase,
+    collection_name: Cow<'a, str>,
+    provider: &'a str,
+    code: &'a str,
+    code_verifier: &'a str,
+    redirect_url: &'a str,
+    create_data: Option<D>,
+}
+
+impl<'a> Collection<'a> {
+    /// Authenticates a user with the `OAuth2` authorization code returned by a provider.
+    ///
+    /// The provider's authorization URL, `state` and `code_verifier` are obtained with
+    /// [`Collection::list_auth_methods`]. Once the user is redirected back to your `redirect_url`,
+    /// check that the returned `state` matches before calling this method with the returned `code`.
+    ///
+    /// This function returns a `CollectionAuthWithOAuth2Builder`, which allows you to specify
+    /// the data used to create the auth record on first login before calling `.call().await`.
+    ///
+    /// Upon successful authentication, the client's internal auth store is updated with the
+    /// authentication token and user information, which will be automatically included in
+    /// subsequent API requests.
+    ///
+    /// # Parameters
+    ///
+    /// * `provider`: The name of the `OAuth2` provider *(example: `google`)*.
+    /// * `code`: The authorization code returned by the provider.
+    /// * `code_verifier`: The PKCE code verifier returned by [`Collection::list_auth_methods`].
+    /// * `redirect_url`: The redirect URL sent to the provider with the authorization URL.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use pocketbase_rs::PocketBase;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct NewUser {
+    ///     name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     // ...
+    ///
+    ///     let auth_data = pb
+    ///         .collection("users")?
+    ///         .auth_with_oauth2_code("google", &code, &code_verifier, "https://example.com/oauth2-redirect")
+    ///         .create_data(NewUser { name: "Vulpes".to_string() })
+    ///         .call()
+    ///         .await?;
+    ///
+    ///     println!("Authenticated as: {}", auth_data.auth_store.record.email);
+    ///     println!("Provider name: {}", auth_data.meta.name);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn auth_with_oauth2_code(
+        self,
+        provider: &'a str,
+        code: &'a str,
+        code_verifier: &'a str,
+        redirect_url: &'a str,
+    ) -> CollectionAuthWithOAuth2Builder<'a> {
+        CollectionAuthWithOAuth2Builder {
+            client: self.client,
+            collection_name: self.name,
+            provider,
+            code,
+            code_verifier,
+            redirect_url,
+            create_data: None,
+        }
+    }
+}
+
+impl<'a, D: Serialize> CollectionAuthWithOAuth2Builder<'a, D> {
+    /// Data used to create the auth record, if it doesn't exist yet.
+    ///
+    /// Ignored when the user already has an auth record.
+    pub fn create_data<T: Serialize>(
+        self,
+        create_data: T,
+    ) -> CollectionAuthWithOAuth2Builder<'a, T> {
+        CollectionAuthWithOAuth2Builder {
+            client: self.client,
+            collection_name: self.collection_name,
+            provider: self.provider,
+            code: self.code,
+            code_verifier: self.code_verifier,
+            redirect_url: self.redirect_url,
+            create_data: Some(create_data),
+        }
+    }
+
+    /// Sends the request and returns the response.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `AuthenticationError` if:
+    ///
+    /// - `InvalidCredentials`: The authorization code or code verifier was refused
+    /// - `BadRequest`: A field of the request was refused *(example: an unknown provider)*
+    /// - `MfaRequired`: The collection requires a second authentication factor
+    /// - `HttpError`: Network or connection issues occurred
+    /// - `UnexpectedResponse`: The server response was not in the expected format
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "auth_with_oauth2",
+            skip_all,
+            fields(
+                collection = %self.collection_name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn call(self) -> Result<OAuth2AuthResponse, AuthenticationError> {
+        let url = format!(
+            "{}/api/collections/{}/auth-with-oauth2",
+            self.client.base_url, self.collection_name
+        );
+
+        let credentials = OAuth2Credentials {
+            provider: self.provider,
+            code: self.code,
+            code_verifier: self.code_verifier,
+            redirect_url: self.redirect_url,
+            create_data: self.create_data,
+        };
+
+        let auth_data = self
+            .client
+            .send_and_decode_without_refresh::<OAuth2AuthResponse>(
+                self.client.request_post(&url).json(&credentials),
+            )
+            .await
+            .map_err(|error| match error.kind() {
+                // {
+                //     "status": 400,
+                //     "message": "Failed to authenticate.",
+                //     "data": {}
+                // }
+                ErrorKind::BadRequest if error.data().is_empty() => {
+                    AuthenticationError::InvalidCredentials(error)
+                }
+                // {
+                //     "status": 400,
+                //     "message": "An error occurred while validating the submitted data.",
+                //     "data": {
+                //       "provider": {
+                //         "code": "validation_invalid_provider",
+                //         "message": "Provider with name \"unknown\" is missing or is not enabled."
+                //       }
+                //     }
+                // }
+                ErrorKind::BadRequest => AuthenticationError::BadRequest(error),
+                _ => error.into(),
+            })?;
+
+        self.client.update_auth_store(auth_data.auth_store.clone());
+
+        Ok(auth_data)
+    }
+}
@@ -0,0 +1,273 @@
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::error::{Error, ErrorKind};
+use crate::{AuthStore, Collection};
+
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OtpCredentials<'a> {
+    otp_id: &'a str,
+    password: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mfa_id: Option<&'a str>,
+}
+
+/// Represents the various errors that can be obtained after an `auth_with_otp` request.
+#[derive(Error, Debug)]
+pub enum AuthWithOtpError {
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
+    ///
+    /// The one-time password is wrong, has expired, or was already used.
+    /// A new one must be requested with [`Collection::request_otp`].
+    #[error("Authentication failed: Invalid or expired one-time password.")]
+    InvalidOrExpiredOtp(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") HTTP error response.
+    ///
+    /// The one-time password is valid, but multi-factor authentication is enabled for this collection.
+    /// The authentication must be completed with another method, passing the given `mfa_id` to
+    /// [`Collection::auth_with_password_mfa`].
+    #[error("Authentication incomplete: Multi-factor authentication is required.")]
+    MfaRequired {
+        /// Id of the multi-factor authentication session.
+        mfa_id: String,
+        /// The error returned by `PocketBase`.
+        error: Error,
+    },
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
+    ///
+    /// The `otp_id` and/or the password are empty.
+    #[error("One or more fields were not validated : {:?}", .0.data())]
+    BadRequest(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
+    ///
+    /// One-time password authentication is not enabled for this collection.
+    #[error("One-time password authentication is not enabled for this collection.")]
+    Forbidden(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
+    ///
+    /// The collection doesn't exist or is not an auth collection.
+    #[error("The collection doesn't exist or is not an auth collection.")]
+    NotFound(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [429 Too Many Requests]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/429") HTTP error response.
+    ///
+    /// Too many authentication attempts were made. Wait before retrying.
+    #[error("Too many authentication attempts. Please wait before retrying.")]
+    TooManyRequests(Error),
+    /// Communication with the `PocketBase` API failed.
+    ///
+    /// This could be caused by an internet outage, an error in the link given to the `PocketBase` SDK
+    /// and similar errors.
+    #[error("The communication with the PocketBase API failed: {0}")]
+    Unreachable(Error),
+    /// The response could not be parsed into the expected data structure.
+    #[error("Could not parse response into the expected data structure: {0}")]
+    ParseError(Error),
+    /// An unexpected error occurred.
+    /// The response from the `PocketBase` instance API was unexpected.
+    /// If you think its an error, please [open an issue on GitHub]("https://github.com/fromhorizons/pocketbase-rs/issues").
+    #[error("An unhandled status code was returned by the PocketBase API: {0}")]
+    UnexpectedResponse(Error),
+}
+
+impl From<Error> for AuthWithOtpError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            // {
+            //     "status": 400,
+            //     "message": "Invalid or expired OTP",
+            //     "data": {}
+            // }
+            ErrorKind::BadRequest if error.data().is_empty() => Self::InvalidOrExpiredOtp(error),
+            ErrorKind::BadRequest => Self::BadRequest(error),
+            ErrorKind::MfaRequired => Self::MfaRequired {
+                mfa_id: error.mfa_id().unwrap_or_default().to_owned(),
+                error,
+            },
+            ErrorKind::Forbidden => Self::Forbidden(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::TooManyRequests => Self::TooManyRequests(error),
+            ErrorKind::Unreachable => Self::Unreachable(error),
+            ErrorKind::ParseError => Self::ParseError(error),
+            _ => Self::UnexpectedResponse(error),
+        }
+    }
+}
+
+impl Collection<'_> {
+    /// Authenticates a Client user with a one-time password received by email.
+    ///
+    /// The `otp_id` is obtained with [`Collection::request_otp`].
+    ///
+    /// Upon successful authentication, the client's internal auth store is updated with the
+    /// authentication token and user information, which will be automatically included in
+    /// subsequent API requests.
+    ///
+    /// # Parameters
+    ///
+    /// * `otp_id`: The id returned by [`Collection::request_otp`].
+    /// * `password`: The one-time password received by email.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use pocketbase_rs::PocketBase;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     let otp_request = pb.collection("users")?.request_otp("test@domain.com").await?;
+    ///
+    ///     // ...
+    ///
+    ///     let auth_data = pb
+    ///         .collection("users")?
+    ///         .auth_with_otp(&otp_request.otp_id, "123456")
+    ///         .await?;
+    ///
+    ///     println!("Authenticated as: {}", auth_data.record.email);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the server fails (`AuthWithOtpError::Unreachable`).
+    /// - The one-time password is wrong or expired (`AuthWithOtpError::InvalidOrExpiredOtp`).
+    /// - The collection requires a second authentication factor (`AuthWithOtpError::MfaRequired`).
+    /// - The `otp_id` or password is empty (`AuthWithOtpError::BadRequest`).
+    /// - One-time password authentication is disabled (`AuthWithOtpError::Forbidden`).
+    /// - The collection is not found (`AuthWithOtpError::NotFound`).
+    /// - The request was rate limited (`AuthWithOtpError::TooManyRequests`).
+    /// - The server responds with an unexpected status (`AuthWithOtpError::UnexpectedResponse`).
+    /// - The response could not be parsed into the expected data structure (`AuthWithOtpError::ParseError`).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "auth_with_otp",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn auth_with_otp(
+        &self,
+        otp_id: &str,
+        password: &str,
+    ) -> Result<AuthStore, AuthWithOtpError> {
+        self.send_otp_credentials(OtpCredentials {
+            otp_id,
+            password,
+            mfa_id: None,
+        })
+        .await
+    }
+
+    /// Completes a multi-factor authentication with a one-time password received by email.
+    ///
+    /// The `mfa_id` is the one returned by the first authentication method, in the
+    /// [`crate::AuthenticationError::MfaRequired`] error.
+    ///
+    /// Upon successful authentication, the client's internal auth store is updated the same
+    /// way as with [`Collection::auth_with_otp`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use pocketbase_rs::{AuthenticationError, PocketBase};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     let mfa_id = match pb
+    ///         .collection("users")?
+    ///         .auth_with_password("test@domain.com", "secure-password")
+    ///         .await
+    ///     {
+    ///         Err(AuthenticationError::MfaRequired { mfa_id, .. }) => mfa_id,
+    ///         // Multi-factor authentication is not enabled.
+    ///         Ok(_) => return Ok(()),
+    ///         Err(error) => return Err(error.into()),
+    ///     };
+    ///
+    ///     let otp_request = pb.collection("users")?.request_otp("test@domain.com").await?;
+    ///
+    ///     // ...
+    ///
+    ///     let auth_data = pb
+    ///         .collection("users")?
+    ///         .auth_with_otp_mfa(&otp_request.otp_id, &password_received_by_email, &mfa_id)
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as [`Collection::auth_with_otp`].
+    /// `InvalidOrExpiredOtp` is also returned when the `mfa_id` is invalid or has expired.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "auth_with_otp",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn auth_with_otp_mfa(
+        &self,
+        otp_id: &str,
+        password: &str,
+        mfa_id: &str,
+    ) -> Result<AuthStore, AuthWithOtpError> {
+        self.send_otp_credentials(OtpCredentials {
+            otp_id,
+            password,
+            mfa_id: Some(mfa_id),
+        })
+        .await
+    }
+
+    async fn send_otp_credentials(
+        &self,
+        credentials: OtpCredentials<'_>,
+    ) -> Result<AuthStore, AuthWithOtpError> {
+        let endpoint = format!(
+            "{}/api/collections/{}/auth-with-otp",
+            self.client.base_url, self.name
+        );
+
+        let auth_store = self
+            .client
+            .send_and_decode_without_refresh::<AuthStore>(
+                self.client.request_post_json(&endpoint, &credentials),
+            )
+            .await?;
+
+        self.client.update_auth_store(auth_store.clone());
+
+        Ok(auth_store)
+    }
+}
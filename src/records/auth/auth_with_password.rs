@@ -1,13 +1,16 @@
 use serde::Serialize;
-use serde_json::Value;
 use thiserror::Error;
 
-use crate::{AuthStore, Collection, ErrorResponse};
+use crate::error::{Error, ErrorKind};
+use crate::{AuthStore, Collection};
 
 #[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 struct Credentials<'a> {
     pub(crate) identity: &'a str,
     pub(crate) password: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) mfa_id: Option<&'a str>,
 }
 
 /// Represents errors that can occur during the authentication process with the `PocketBase` API.
@@ -21,7 +24,28 @@ pub enum AuthenticationError {
     ///
     /// Tip: The credentials you provided may be incorrect.
     #[error("Authentication failed: Invalid Credentials. Given email and/or password is wrong.")]
-    InvalidCredentials,
+    InvalidCredentials(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response
+    /// with field errors.
+    ///
+    /// Returned by [`Collection::auth_with_oauth2_code`] when a field of the request was refused
+    /// *(example: an unknown provider, or a `create_data` that didn't pass the validation)*.
+    #[error("One or more fields were not validated : {:?}", .0.data())]
+    BadRequest(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") HTTP error response.
+    ///
+    /// The credentials are valid, but multi-factor authentication is enabled for this collection.
+    /// The authentication must be completed with another method, passing the given `mfa_id` to
+    /// [`Collection::auth_with_otp_mfa`] or [`Collection::auth_with_password_mfa`].
+    #[error("Authentication incomplete: Multi-factor authentication is required.")]
+    MfaRequired {
+        /// Id of the multi-factor authentication session.
+        mfa_id: String,
+        /// The error returned by `PocketBase`.
+        error: Error,
+    },
     /// Email and/or Password cannot be empty.
     ///
     /// This variant indicates that certain fields in the authentication request need to be validated.
@@ -37,6 +61,8 @@ pub enum AuthenticationError {
         identity: bool,
         /// Is password blank.
         password: bool,
+        /// The error returned by `PocketBase`.
+        error: Error,
     },
     /// The provided identity must be an email address.
     ///
@@ -44,26 +70,127 @@ pub enum AuthenticationError {
     /// does not conform to the expected email format. The `PocketBase` API requires the identity to
     /// be a valid email address for authentication.
     #[error("Authentication failed. Given identity is not a valid email.")]
-    IdentityMustBeEmail,
+    IdentityMustBeEmail(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
+    ///
+    /// This authentication method is not enabled for this collection.
+    #[error(
+        "Authentication failed: This authentication method is not enabled for this collection."
+    )]
+    Forbidden(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
+    ///
+    /// The collection doesn't exist or is not an auth collection.
+    #[error("Authentication failed: The collection doesn't exist or is not an auth collection.")]
+    NotFound(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [429 Too Many Requests]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/429") HTTP error response.
+    ///
+    /// Too many authentication attempts were made. Wait before retrying.
+    #[error("Too many authentication attempts. Please wait before retrying.")]
+    TooManyRequests(Error),
     /// An HTTP error occurred while communicating with the `PocketBase` API.
     ///
-    /// This variant wraps a [`reqwest::Error`] and indicates that the request could not be completed
-    /// due to network issues, invalid URL, timeouts, etc.
+    /// This variant indicates that the request could not be completed due to network issues,
+    /// invalid URL, timeouts, etc. The [`reqwest::Error`] is the source of the held [`Error`].
     #[error("Authentication failed. Couldn't reach the PocketBase API: {0}")]
-    HttpError(reqwest::Error),
+    HttpError(Error),
     /// When something unexpected was returned by the `PocketBase` REST API.
     ///
     /// Would usually mean that there is an error somewhere in this API wrapper.
     #[error("Authentication failed due to an unexpected response. Usually means a problem in the PocketBase API's wrapper.")]
-    UnexpectedResponse,
+    UnexpectedResponse(Error),
     /// Occurs when you try to authenticate a `PocketBase` client without providing the collection name.
     #[error("Authentication failed due to missing collection name. [Example: PocketBaseClientBuilder::new(\"\")")]
     MissingCollection,
 }
 
+impl From<Error> for AuthenticationError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            ErrorKind::MfaRequired => Self::MfaRequired {
+                mfa_id: error.mfa_id().unwrap_or_default().to_owned(),
+                error,
+            },
+            ErrorKind::BadRequest => Self::from_bad_request(error),
+            ErrorKind::Forbidden => Self::Forbidden(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::TooManyRequests => Self::TooManyRequests(error),
+            ErrorKind::Unreachable => Self::HttpError(error),
+            _ => Self::UnexpectedResponse(error),
+        }
+    }
+}
+
+impl AuthenticationError {
+    /// Finds the reason of a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400")
+    /// response from its field errors.
+    fn from_bad_request(error: Error) -> Self {
+        let data = error.data();
+
+        // {
+        //     "status": 400,
+        //     "message": "Failed to authenticate.",
+        //     "data": {}
+        // }
+        if data.is_empty() {
+            return Self::InvalidCredentials(error);
+        }
+
+        let identity_error = data
+            .iter()
+            .find(|field| field.name == "identity")
+            .map(|field| field.code.clone());
+
+        let password_error = data.iter().any(|field| field.name == "password");
+
+        match identity_error.as_deref() {
+            // {
+            //     "status": 400,
+            //     "message": "Something went wrong while processing your request.",
+            //     "data": {
+            //       "identity": {
+            //         "code": "validation_is_email",
+            //         "message": "Must be a valid email address."
+            //       }
+            //     }
+            // }
+            Some("validation_is_email") => Self::IdentityMustBeEmail(error),
+
+            // {
+            //     "status": 400,
+            //     "message": "Something went wrong while processing your request.",
+            //     "data": {
+            //       "identity": {
+            //         "code": "validation_required",
+            //         "message": "Cannot be blank."
+            //       },
+            //       "password": {
+            //         "code": "validation_required",
+            //         "message": "Cannot be blank."
+            //       }
+            //     }
+            // }
+            Some("validation_required") => Self::EmptyField {
+                identity: true,
+                password: password_error,
+                error,
+            },
+            None => Self::EmptyField {
+                identity: false,
+                password: password_error,
+                error,
+            },
+            Some(_) => Self::InvalidCredentials(error),
+        }
+    }
+}
+
 impl From<reqwest::Error> for AuthenticationError {
     fn from(error: reqwest::Error) -> Self {
-        Self::HttpError(error)
+        Self::HttpError(error.into())
     }
 }
 
@@ -93,8 +220,12 @@ impl Collection<'_> {
     /// This function will return an `AuthenticationError` if:
     ///
     /// - `InvalidCredentials`: The provided email/password combination is incorrect
+    /// - `MfaRequired`: The collection requires a second authentication factor
     /// - `EmptyField`: Either the identity or password field is empty
     /// - `IdentityMustBeEmail`: The identity field doesn't contain a valid email format
+    /// - `Forbidden`: Password authentication is not enabled for this collection
+    /// - `NotFound`: The collection doesn't exist or is not an auth collection
+    /// - `TooManyRequests`: Too many authentication attempts were made
     /// - `HttpError`: Network or connection issues occurred
     /// - `UnexpectedResponse`: The server response was not in the expected format
     /// - `MissingCollection`: No collection name was provided
@@ -107,10 +238,10 @@ impl Collection<'_> {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let mut pb = PocketBase::new("http://localhost:8090");
+    ///     let pb = PocketBase::new("http://localhost:8090");
     ///
     ///     // Authenticate with a users collection
-    ///     let auth_data = pb.collection("users")
+    ///     let auth_data = pb.collection("users")?
     ///         .auth_with_password("test@domain.com", "secure-password")
     ///         .await?;
     ///
@@ -118,7 +249,7 @@ impl Collection<'_> {
     ///     println!("Token: {}", auth_data.token);
     ///
     ///     // The token is now automatically included in future requests
-    ///     let profile = pb.collection("profiles")
+    ///     let profile = pb.collection("profiles")?
     ///         .get_one::<Profile>("some_id")
     ///         .call()
     ///         .await?;
@@ -126,109 +257,121 @@ impl Collection<'_> {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "auth_with_password",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn auth_with_password(
-        &mut self,
+        &self,
+        identity: &str,
+        password: &str,
+    ) -> Result<AuthStore, AuthenticationError> {
+        self.send_password_credentials(Credentials {
+            identity,
+            password,
+            mfa_id: None,
+        })
+        .await
+    }
+
+    /// Completes a multi-factor authentication with the Client user's email and password.
+    ///
+    /// The `mfa_id` is the one returned by the first authentication method, in the
+    /// [`AuthenticationError::MfaRequired`] or [`crate::AuthWithOtpError::MfaRequired`] error.
+    ///
+    /// Upon successful authentication, the client's internal auth store is updated the same
+    /// way as with [`Collection::auth_with_password`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as [`Collection::auth_with_password`].
+    /// `InvalidCredentials` is also returned when the `mfa_id` is invalid or has expired.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    /// use pocketbase_rs::{AuthWithOtpError, PocketBase};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     let otp_request = pb.collection("users")?.request_otp("test@domain.com").await?;
+    ///
+    ///     // ...
+    ///
+    ///     let mfa_id = match pb
+    ///         .collection("users")?
+    ///         .auth_with_otp(&otp_request.otp_id, &password_received_by_email)
+    ///         .await
+    ///     {
+    ///         Err(AuthWithOtpError::MfaRequired { mfa_id, .. }) => mfa_id,
+    ///         // Multi-factor authentication is not enabled.
+    ///         Ok(_) => return Ok(()),
+    ///         Err(error) => return Err(error.into()),
+    ///     };
+    ///
+    ///     let auth_data = pb
+    ///         .collection("users")?
+    ///         .auth_with_password_mfa("test@domain.com", "secure-password", &mfa_id)
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "auth_with_password",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn auth_with_password_mfa(
+        &self,
         identity: &str,
         password: &str,
+        mfa_id: &str,
+    ) -> Result<AuthStore, AuthenticationError> {
+        self.send_password_credentials(Credentials {
+            identity,
+            password,
+            mfa_id: Some(mfa_id),
+        })
+        .await
+    }
+
+    async fn send_password_credentials(
+        &self,
+        credentials: Credentials<'_>,
     ) -> Result<AuthStore, AuthenticationError> {
         let uri = format!(
             "{}/api/collections/{}/auth-with-password",
             self.client.base_url, self.name
         );
 
-        let credentials = Credentials { identity, password };
-
-        let response = self
+        let auth_store = self
             .client
-            .request_post_json(&uri, &credentials)
-            .send()
+            .send_and_decode_without_refresh::<AuthStore>(
+                self.client.request_post_json(&uri, &credentials),
+            )
             .await?;
 
-        if response.status().is_success() {
-            let auth_store = response.json::<AuthStore>().await?;
-
-            self.client.update_auth_store(auth_store.clone());
-
-            return Ok(auth_store);
-        }
-
-        if response.status() == reqwest::StatusCode::BAD_REQUEST {
-            let error_response: ErrorResponse =
-                response.json().await.unwrap_or_else(|_| ErrorResponse {
-                    code: 400,
-                    message: "Unknown error".to_string(),
-                    data: None,
-                });
-
-            if let Some(ref data) = error_response.data {
-                // {
-                //     "code": 400,
-                //     "message": "Failed to authenticate.",
-                //     "data": {}
-                // }
-                if data.as_object().is_some_and(serde_json::Map::is_empty) {
-                    return Err(AuthenticationError::InvalidCredentials);
-                }
-
-                // Check for specific field validation errors
-                let identity_error = data
-                    .get("identity")
-                    .and_then(|v| v.get("code").and_then(Value::as_str));
-
-                match identity_error {
-                    // {
-                    //     "code": 400,
-                    //     "message": "Something went wrong while processing your request.",
-                    //     "data": {
-                    //       "identity": {
-                    //         "code": "validation_is_email",
-                    //         "message": "Must be a valid email address."
-                    //       }
-                    //     }
-                    // }
-                    Some("validation_is_email") => {
-                        return Err(AuthenticationError::IdentityMustBeEmail)
-                    }
-
-                    // {
-                    //     "code": 400,
-                    //     "message": "Something went wrong while processing your request.",
-                    //     "data": {
-                    //       "identity": {
-                    //         "code": "validation_required",
-                    //         "message": "Cannot be blank."
-                    //       },
-                    //       "password": {
-                    //         "code": "validation_required",
-                    //         "message": "Cannot be blank."
-                    //       }
-                    //     }
-                    // }
-                    Some("validation_required") => {
-                        return Err(AuthenticationError::EmptyField {
-                            identity: identity_error.is_some(),
-                            password: data.get("password").is_some(),
-                        })
-                    }
-                    None => {
-                        let password_error = data.get("password").is_some();
-                        return Err(AuthenticationError::EmptyField {
-                            identity: false,
-                            password: password_error,
-                        });
-                    }
-                    _ => {}
-                }
-            }
-
-            // {
-            //     "code": 400,
-            //     "message": "Failed to authenticate.",
-            //     "data": {}
-            // }
-            return Err(AuthenticationError::InvalidCredentials);
-        }
+        self.client.update_auth_store(auth_store.clone());
 
-        Err(AuthenticationError::UnexpectedResponse)
+        Ok(auth_store)
     }
 }
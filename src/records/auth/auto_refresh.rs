@@ -0,0 +1,274 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use super::AuthStore;
+use crate::PocketBase;
+
+impl PocketBase {
+    /// Enables the automatic refresh of the auth token.
+    ///
+    /// Once enabled, the token is renewed with `auth-refresh` before sending a request when
+    /// it expires in less than `threshold`. If a request is still refused with a
+    /// [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") response,
+    /// the token is refreshed once and the request is sent again.
+    ///
+    /// If the server refuses the refresh with a `401 Unauthorized` or `403 Forbidden`, the
+    /// token is no longer valid and the client is logged out, as with [`PocketBase::logout`].
+    ///
+    /// This setting applies to this client, and to the clones made afterwards.
+    ///
+    /// Tokens of clients returned by [`crate::Collection::impersonate`] can't be refreshed,
+    /// and are never refreshed automatically.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    /// use std::time::Duration;
+    ///
+    /// use pocketbase_rs::PocketBase;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let mut pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     pb.enable_auto_refresh(Duration::from_secs(5 * 60));
+    ///
+    ///     pb.collection("users")?
+    ///         .auth_with_password("test@domain.com", "secure-password")
+    ///         .await?;
+    ///
+    ///     // The token is now refreshed when needed by the requests of `pb`.
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub const fn enable_auto_refresh(&mut self, threshold: Duration) {
+        self.auto_refresh = Some(threshold);
+    }
+
+    /// Disables the automatic refresh of the auth token.
+    ///
+    /// See [`PocketBase::enable_auto_refresh`].
+    pub const fn disable_auto_refresh(&mut self) {
+        self.auto_refresh = None;
+    }
+
+    /// Refreshes the token if the automatic refresh is enabled and the token expires soon.
+    pub(crate) async fn refresh_auth_if_expiring(&self) {
+        let Some(threshold) = self.auto_refresh else {
+            return;
+        };
+
+        let Some(session) = self.auth.session() else {
+            return;
+        };
+
+        let Some(expires_at) = session.auth_store.expires_at() else {
+            return;
+        };
+
+        // Already expired tokens give an error, which is a zero duration.
+        let remaining = (expires_at - Utc::now()).to_std().unwrap_or_default();
+
+        if session.refreshable && remaining <= threshold {
+            self.refresh_auth(&session.auth_store.token).await;
+        }
+    }
+
+    /// Replaces `stale_token` with a refreshed token.
+    ///
+    /// Returns `true` if the client now holds another token than `stale_token`,
+    /// possibly refreshed meanwhile by a concurrent request.
+    ///
+    /// The auth store is cleared when the server refuses the refresh with a `401` or `403`.
+    pub(crate) async fn refresh_auth(&self, stale_token: &str) -> bool {
+        let _guard = self.auth.refresh_lock.lock().await;
+
+        let Some(session) = self.auth.session() else {
+            return false;
+        };
+
+        if session.auth_store.token != stale_token {
+            return true;
+        }
+
+        if !session.refreshable {
+            return false;
+        }
+
+        let url = format!(
+            "{}/api/collections/{}/auth-refresh",
+            self.base_url, session.auth_store.record.collection_name
+        );
+
+        // Boxed, as the request goes through `dispatch`, which may itself call this method.
+        let refreshed = Box::pin(self.send_and_decode_without_refresh::<AuthStore>(
+            self.reqwest_client.post(&url).bearer_auth(stale_token),
+        ))
+        .await;
+
+        let auth_store = match refreshed {
+            Ok(auth_store) => auth_store,
+            Err(error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(%error, "The auth token couldn't be refreshed.");
+
+                // The token was revoked or its record deleted: the session is over.
+                if matches!(
+                    error.status(),
+                    Some(reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN)
+                ) {
+                    self.auth.clear();
+                    self.sync_realtime_auth_token();
+                }
+
+                return false;
+            }
+        };
+
+        self.auth.set(auth_store, true);
+        self.sync_realtime_auth_token();
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    use super::*;
+
+    /// Payload of `{"exp":1700000000,"id":"???>>>"}`, encoded with the `-` and `_` characters of
+    /// base64url, and without its `=` padding.
+    const PAYLOAD: &str = "eyJleHAiOjE3MDAwMDAwMDAsImlkIjoiPz8_Pj4-In0";
+
+    fn auth_store(token: &str) -> AuthStore {
+        serde_json::from_value(auth_store_json(token)).unwrap()
+    }
+
+    fn auth_store_json(token: &str) -> serde_json::Value {
+        json!({
+            "token": token,
+            "record": {
+                "id": "u1",
+                "collectionId": "_pb_users_auth_",
+                "collectionName": "users",
+                "created": "2024-01-31 12:00:00.000Z",
+                "updated": "2024-01-31 12:00:00.000Z",
+                "email": "fox@domain.com",
+                "emailVisibility": false,
+                "verified": true,
+            },
+        })
+    }
+
+    #[test]
+    fn reads_the_expiration_of_unpadded_tokens() {
+        let expires_at = auth_store(&format!("header.{PAYLOAD}.signature")).expires_at();
+
+        assert_eq!(expires_at, DateTime::from_timestamp(1_700_000_000, 0));
+    }
+
+    #[test]
+    fn reads_the_expiration_of_padded_tokens() {
+        let expires_at = auth_store(&format!("header.{PAYLOAD}=.signature")).expires_at();
+
+        assert_eq!(expires_at, DateTime::from_timestamp(1_700_000_000, 0));
+    }
+
+    #[test]
+    fn ignores_tokens_without_valid_expiration() {
+        // {"id":"u1"}
+        assert_eq!(
+            auth_store("header.eyJpZCI6InUxIn0.signature").expires_at(),
+            None
+        );
+        // {"exp":"soon"}
+        assert_eq!(
+            auth_store("header.eyJleHAiOiJzb29uIn0.signature").expires_at(),
+            None
+        );
+        assert_eq!(
+            auth_store("header.not base64!.signature").expires_at(),
+            None
+        );
+        assert_eq!(auth_store("not a jwt").expires_at(), None);
+    }
+
+    #[tokio::test]
+    async fn logs_out_when_the_refresh_is_refused() {
+        let server = MockServer::start_async().await;
+        let refresh = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/api/collections/users/auth-refresh");
+                then.status(401)
+                    .json_body(json!({ "status": 401, "message": "Expired token.", "data": {} }));
+            })
+            .await;
+
+        let pb = PocketBase::new(&server.base_url());
+        pb.update_auth_store(auth_store(&format!("header.{PAYLOAD}.signature")));
+
+        assert!(
+            !pb.refresh_auth(&format!("header.{PAYLOAD}.signature"))
+                .await
+        );
+        assert!(pb.auth_store().is_none());
+        assert_eq!(refresh.hits_async().await, 1);
+    }
+
+    #[tokio::test]
+    async fn retries_once_with_the_refreshed_token_after_a_401() {
+        let server = MockServer::start_async().await;
+        let stale = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/api/collections/articles/records/r1")
+                    .header("Authorization", "Bearer token-a");
+                then.status(401)
+                    .json_body(json!({ "status": 401, "message": "Expired token.", "data": {} }));
+            })
+            .await;
+        let fresh = server
+            .mock_async(|when, then| {
+                when.method(GET)
+                    .path("/api/collections/articles/records/r1")
+                    .header("Authorization", "Bearer token-b");
+                then.status(200).json_body(json!({ "title": "Foxes" }));
+            })
+            .await;
+        let refresh = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/api/collections/users/auth-refresh")
+                    .header("Authorization", "Bearer token-a");
+                then.status(200).json_body(auth_store_json("token-b"));
+            })
+            .await;
+
+        let mut pb = PocketBase::new(&server.base_url());
+        pb.enable_auto_refresh(Duration::from_secs(60));
+        // Without expiration, the token is only refreshed after the `401`.
+        pb.update_auth_store(auth_store("token-a"));
+
+        let record = pb
+            .collection("articles")
+            .unwrap()
+            .get_one::<serde_json::Value>("r1")
+            .call()
+            .await
+            .unwrap();
+
+        assert_eq!(record, json!({ "title": "Foxes" }));
+        assert_eq!(stale.hits_async().await, 1);
+        assert_eq!(refresh.hits_async().await, 1);
+        assert_eq!(fresh.hits_async().await, 1);
+        assert_eq!(pb.auth_store().unwrap().token, "token-b");
+    }
+}
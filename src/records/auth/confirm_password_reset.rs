@@ -0,0 +1,146 @@
+use serde::de::IgnoredAny;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::error::{Error, ErrorKind};
+use crate::Collection;
+
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfirmPasswordResetBody<'a> {
+    token: &'a str,
+    password: &'a str,
+    password_confirm: &'a str,
+}
+
+/// Represents the various errors that can be obtained after a `confirm_password_reset` request.
+#[derive(Error, Debug)]
+pub enum ConfirmPasswordResetError {
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
+    ///
+    /// The token is invalid or has expired, or the new password was refused
+    /// *(example: too short, or not matching its confirmation)*.
+    #[error("One or more fields were not validated : {:?}", .0.data())]
+    BadRequest(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
+    ///
+    /// The collection doesn't exist or is not an auth collection.
+    #[error("The collection doesn't exist or is not an auth collection.")]
+    NotFound(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [429 Too Many Requests]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/429") HTTP error response.
+    ///
+    /// The server is rate limiting requests. Wait before retrying.
+    #[error("Too many requests. Please wait before retrying.")]
+    TooManyRequests(Error),
+    /// Communication with the `PocketBase` API failed.
+    ///
+    /// This could be caused by an internet outage, an error in the link given to the `PocketBase` SDK
+    /// and similar errors.
+    #[error("The communication with the PocketBase API failed: {0}")]
+    Unreachable(Error),
+    /// The response could not be parsed into the expected data structure.
+    #[error("Could not parse response into the expected data structure: {0}")]
+    ParseError(Error),
+    /// An unexpected error occurred.
+    /// The response from the `PocketBase` instance API was unexpected.
+    /// If you think its an error, please [open an issue on GitHub]("https://github.com/fromhorizons/pocketbase-rs/issues").
+    #[error("An unhandled status code was returned by the PocketBase API: {0}")]
+    UnexpectedResponse(Error),
+}
+
+impl From<Error> for ConfirmPasswordResetError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            ErrorKind::BadRequest => Self::BadRequest(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::TooManyRequests => Self::TooManyRequests(error),
+            ErrorKind::Unreachable => Self::Unreachable(error),
+            ErrorKind::ParseError => Self::ParseError(error),
+            _ => Self::UnexpectedResponse(error),
+        }
+    }
+}
+
+impl Collection<'_> {
+    /// Sets a new password for a user.
+    ///
+    /// Uses the token sent in the email requested with [`Collection::request_password_reset`].
+    /// Previously issued auth tokens of this user are invalidated.
+    ///
+    /// # Parameters
+    ///
+    /// * `token`: The password reset token received by email.
+    /// * `password`: The new password.
+    /// * `password_confirm`: The new password confirmation.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use pocketbase_rs::PocketBase;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     // ...
+    ///
+    ///     pb.collection("users")?
+    ///         .confirm_password_reset("RESET_TOKEN", "new-password", "new-password")
+    ///         .await?;
+    ///
+    ///     println!("The password was changed successfully.");
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the server fails (`ConfirmPasswordResetError::Unreachable`).
+    /// - The token or the new password is refused (`ConfirmPasswordResetError::BadRequest`).
+    /// - The collection is not found (`ConfirmPasswordResetError::NotFound`).
+    /// - The request was rate limited (`ConfirmPasswordResetError::TooManyRequests`).
+    /// - The server responds with an unexpected status (`ConfirmPasswordResetError::UnexpectedResponse`).
+    /// - The response could not be parsed (`ConfirmPasswordResetError::ParseError`).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "confirm_password_reset",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn confirm_password_reset(
+        &self,
+        token: &str,
+        password: &str,
+        password_confirm: &str,
+    ) -> Result<(), ConfirmPasswordResetError> {
+        let endpoint = format!(
+            "{}/api/collections/{}/confirm-password-reset",
+            self.client.base_url, self.name
+        );
+
+        let body = ConfirmPasswordResetBody {
+            token,
+            password,
+            password_confirm,
+        };
+
+        self.client
+            .send_and_decode::<IgnoredAny>(self.client.request_post_json(&endpoint, &body))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
-use serde::Deserialize;
+use std::borrow::Cow;
+use std::sync::Arc;
+
 use thiserror::Error;
 
 use super::AuthStore;
+use crate::error::{Error, ErrorKind};
 use crate::{Collection, PocketBase};
 
 /// Represents the various errors that can be obtained after a `impersonate` request.
@@ -12,61 +15,62 @@ pub enum ImpersonateError {
     ///
     /// The request requires valid record authorization token to be set.
     #[error("Bad Request: The request requires valid record authorization token to be set.")]
-    BadRequest,
+    BadRequest(Error),
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") HTTP error response.
     ///
     /// The request requires valid record authorization token.
     #[error("The request requires valid record authorization token.")]
-    Unauthorized,
+    Unauthorized(Error),
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
     ///
     /// The authorized record is not allowed to perform this action.
     /// Are you impersonating a user from a non-superuser account?
     #[error("The authorized record is not allowed to perform this action. Are you impersonating a user from a non-superuser account?")]
-    Forbidden,
+    Forbidden(Error),
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
     ///
     /// The requested resource wasn't found.
     /// The given user id is probably wrong.
     #[error("The requested resource wasn't found.")]
-    NotFound,
+    NotFound(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [429 Too Many Requests]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/429") HTTP error response.
+    ///
+    /// The server is rate limiting requests. Wait before retrying.
+    #[error("Too many requests. Please wait before retrying.")]
+    TooManyRequests(Error),
     /// Communication with the `PocketBase` API failed.
     ///
     /// This could be caused by an internet outage, an error in the link given to the `PocketBase` SDK
     /// and similar errors.
     #[error("The communication with the PocketBase API failed: {0}")]
-    Unreachable(String),
+    Unreachable(Error),
     /// The response from the `PocketBase` instance API was unexpected.
     /// If you think its an error, please [open an issue on GitHub]("https://github.com/fromhorizons/pocketbase-rs/issues").
     #[error("An unhandled status code was returned by the PocketBase API: {0}")]
-    UnexpectedResponse(String),
-}
-
-#[derive(Deserialize)]
-struct AuthData {
-    record: AuthDataRecord,
-    token: String,
+    UnexpectedResponse(Error),
 }
 
-#[derive(Default, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct AuthDataRecord {
-    collection_id: String,
-    collection_name: String,
-    id: String,
-    email: String,
-    email_visibility: bool,
-    verified: bool,
-    created: String,
-    updated: String,
+impl From<Error> for ImpersonateError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            ErrorKind::BadRequest => Self::BadRequest(error),
+            ErrorKind::Unauthorized => Self::Unauthorized(error),
+            ErrorKind::Forbidden => Self::Forbidden(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::TooManyRequests => Self::TooManyRequests(error),
+            ErrorKind::Unreachable => Self::Unreachable(error),
+            _ => Self::UnexpectedResponse(error),
+        }
+    }
 }
 
 pub struct CollectionImpersonateBuilder<'a> {
     client: &'a PocketBase,
-    collection_name: &'a str,
+    collection_name: Cow<'a, str>,
     user_id: &'a str,
     duration: Option<String>,
 }
@@ -92,12 +96,12 @@ impl<'a> Collection<'a> {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let mut pb = PocketBase::new("http://localhost:8090");
+    ///     let pb = PocketBase::new("http://localhost:8090");
     ///
     ///     // ...
     ///
     ///     let impersonate_client = pb
-    ///         .collection("articles")
+    ///         .collection("articles")?
     ///         .impersonate("USER_RECORD_ID")
     ///         .duration(3600)
     ///         .await?;
@@ -108,7 +112,7 @@ impl<'a> Collection<'a> {
     ///     }
     ///
     ///     let articles = impersonate_client
-    ///         .collection("articles")
+    ///         .collection("articles")?
     ///         .get_list::<Article>()
     ///         .call()
     ///         .await.;
@@ -121,7 +125,7 @@ impl<'a> Collection<'a> {
     /// }
     /// ```
     #[must_use]
-    pub const fn impersonate(self, user_id: &'a str) -> CollectionImpersonateBuilder<'a> {
+    pub fn impersonate(self, user_id: &'a str) -> CollectionImpersonateBuilder<'a> {
         CollectionImpersonateBuilder {
             client: self.client,
             collection_name: self.name,
@@ -140,8 +144,43 @@ impl CollectionImpersonateBuilder<'_> {
         self
     }
 
-    /// Sends the request and returns the response.
+    /// Sends the request and returns a new client authenticated as the impersonated user.
+    ///
+    /// The new client uses the same HTTP client, retry policy and middlewares as this one.
+    ///
+    /// Only superusers can impersonate users: the request is not sent when the client is not
+    /// authenticated (`ImpersonateError::Unauthorized`), or not authenticated as a superuser
+    /// (`ImpersonateError::Forbidden`).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "impersonate",
+            skip_all,
+            fields(
+                collection = %self.collection_name,
+                record_id = %self.user_id,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn call(self) -> Result<PocketBase, ImpersonateError> {
+        match self.client.auth_store() {
+            None => {
+                return Err(ImpersonateError::Unauthorized(Error::new(
+                    ErrorKind::Unauthorized,
+                    "The client is not authenticated.",
+                )))
+            }
+            Some(auth_store) if !auth_store.is_superuser() => {
+                return Err(ImpersonateError::Forbidden(Error::new(
+                    ErrorKind::Forbidden,
+                    "The client is not authenticated as a superuser.",
+                )))
+            }
+            Some(_) => {}
+        }
+
         let url = format!(
             "{}/api/collections/{}/impersonate/{}",
             self.client.base_url, self.collection_name, self.user_id
@@ -149,43 +188,31 @@ impl CollectionImpersonateBuilder<'_> {
 
         let request = {
             if let Some(duration) = self.duration {
-                self.client
-                    .request_post_form(
-                        &url,
-                        reqwest::multipart::Form::new().text("duration", duration),
-                    )
-                    .send()
-                    .await
+                self.client.request_post_form(
+                    &url,
+                    reqwest::multipart::Form::new().text("duration", duration),
+                )
             } else {
-                self.client.request_post(&url).send().await
+                self.client.request_post(&url)
             }
         };
 
-        match request {
-            Ok(response) => match response.status() {
-                reqwest::StatusCode::OK => {
-                    let Ok(auth_store) = response.json::<AuthStore>().await else {
-                        return Err(ImpersonateError::UnexpectedResponse(
-                            "Couldn't parse API response into Auth Data".to_string(),
-                        ));
-                    };
-
-                    let mut impersonate_client = PocketBase::new(&self.client.base_url());
-                    impersonate_client.update_auth_store(auth_store);
+        let auth_store = self.client.send_and_decode::<AuthStore>(request).await?;
 
-                    Ok(impersonate_client)
-                }
-
-                reqwest::StatusCode::BAD_REQUEST => Err(ImpersonateError::BadRequest),
-                reqwest::StatusCode::UNAUTHORIZED => Err(ImpersonateError::Unauthorized),
-                reqwest::StatusCode::FORBIDDEN => Err(ImpersonateError::Forbidden),
-                reqwest::StatusCode::NOT_FOUND => Err(ImpersonateError::NotFound),
+        // The new client shares the HTTP client, the retry policy and the middlewares of this
+        // one, but has its own auth session and realtime connection.
+        let impersonate_client = PocketBase {
+            base_url: self.client.base_url.clone(),
+            auth: Arc::default(),
+            // Impersonation tokens can't be refreshed.
+            auto_refresh: None,
+            retry_policy: self.client.retry_policy,
+            middlewares: self.client.middlewares.clone(),
+            reqwest_client: self.client.reqwest_client.clone(),
+            realtime: Arc::default(),
+        };
+        impersonate_client.auth.set(auth_store, false);
 
-                _ => Err(ImpersonateError::UnexpectedResponse(
-                    response.status().to_string(),
-                )),
-            },
-            Err(error) => Err(ImpersonateError::Unreachable(error.to_string())),
-        }
+        Ok(impersonate_client)
     }
 }
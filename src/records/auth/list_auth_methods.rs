@@ -0,0 +1,145 @@
+use serde::Deserialize;
+
+use crate::error::RequestError;
+use crate::Collection;
+
+/// The authentication methods allowed by an auth collection.
+///
+/// Returned by [`Collection::list_auth_methods`].
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct AuthMethodsList {
+    /// Identity/password authentication.
+    pub password: PasswordAuthMethod,
+    /// `OAuth2` authentication, with the configured providers.
+    pub oauth2: OAuth2AuthMethod,
+    /// Multi-factor authentication.
+    pub mfa: MfaAuthMethod,
+    /// One-time password authentication.
+    pub otp: OtpAuthMethod,
+}
+
+/// Identity/password authentication options of an auth collection.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PasswordAuthMethod {
+    /// Whether identity/password authentication is enabled.
+    pub enabled: bool,
+    /// The fields that can be used as identity *(example: `email`, `username`)*.
+    pub identity_fields: Vec<String>,
+}
+
+/// `OAuth2` authentication options of an auth collection.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct OAuth2AuthMethod {
+    /// Whether `OAuth2` authentication is enabled.
+    pub enabled: bool,
+    /// The configured `OAuth2` providers.
+    pub providers: Vec<AuthProviderInfo>,
+}
+
+/// An `OAuth2` provider, with everything needed to start its authorization flow.
+///
+/// Redirect the user to [`AuthProviderInfo::auth_url`] followed by your redirect URL,
+/// then keep [`AuthProviderInfo::state`] and [`AuthProviderInfo::code_verifier`] to
+/// validate the callback and call [`Collection::auth_with_oauth2_code`].
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AuthProviderInfo {
+    /// Name of the provider *(example: `google`)*.
+    pub name: String,
+    /// Human readable name of the provider *(example: `Google`)*.
+    pub display_name: String,
+    /// The `state` that must be returned to the redirect URL.
+    pub state: String,
+    /// The authorization URL of the provider, ending with `redirect_uri=`.
+    #[serde(rename = "authURL")]
+    pub auth_url: String,
+    /// The PKCE code verifier, to send along with the authorization code.
+    pub code_verifier: String,
+    /// The PKCE code challenge.
+    pub code_challenge: String,
+    /// The PKCE code challenge method *(example: `S256`)*.
+    pub code_challenge_method: String,
+}
+
+/// Multi-factor authentication options of an auth collection.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct MfaAuthMethod {
+    /// Whether multi-factor authentication is enabled.
+    pub enabled: bool,
+    /// Duration (in seconds) of the MFA session.
+    pub duration: i64,
+}
+
+/// One-time password authentication options of an auth collection.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct OtpAuthMethod {
+    /// Whether one-time password authentication is enabled.
+    pub enabled: bool,
+    /// Duration (in seconds) of validity of a one-time password.
+    pub duration: i64,
+}
+
+impl Collection<'_> {
+    /// Returns the public list with the allowed authentication methods of the collection.
+    ///
+    /// # Errors
+    ///
+    /// This function may return:
+    /// - `RequestError::NotFound` if the collection doesn't exist or is not an auth collection.
+    /// - `RequestError::BadRequest` if the request was refused, with the message of `PocketBase`.
+    /// - `RequestError::TooManyRequests` if the request was rate limited.
+    /// - `RequestError::Unreachable` if the request to the server fails.
+    /// - `RequestError::ParseError` if the response could not be parsed into the expected data structure.
+    /// - `RequestError::Unhandled` for all other error cases, including server errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use pocketbase_rs::PocketBase;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     let auth_methods = pb.collection("users")?.list_auth_methods().await?;
+    ///
+    ///     for provider in auth_methods.oauth2.providers {
+    ///         println!("{}: {}", provider.display_name, provider.auth_url);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "list_auth_methods",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn list_auth_methods(&self) -> Result<AuthMethodsList, RequestError> {
+        let url = format!(
+            "{}/api/collections/{}/auth-methods",
+            self.client.base_url, self.name
+        );
+
+        let auth_methods = self
+            .client
+            .send_and_decode::<AuthMethodsList>(self.client.request_get(&url, None))
+            .await?;
+
+        Ok(auth_methods)
+    }
+}
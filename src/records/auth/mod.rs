@@ -1,16 +1,35 @@
-use serde::Deserialize;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
+pub mod auth_as_superuser;
 pub mod auth_refresh;
 pub mod auth_refresh_for_user;
+pub mod auth_state;
+pub mod auth_store_backend;
+pub mod auth_with_oauth2;
+pub mod auth_with_otp;
 pub mod auth_with_password;
+pub mod auto_refresh;
+pub mod confirm_email_change;
+pub mod confirm_password_reset;
+pub mod confirm_verification;
 pub mod impersonate;
+pub mod list_auth_methods;
+pub mod request_email_change;
+pub mod request_otp;
+pub mod request_password_reset;
 pub mod request_verification;
 
+/// Name of the system collection of the superusers.
+const SUPERUSERS_COLLECTION: &str = "_superusers";
+
 /// Stores authentication details for a `PocketBase` user.
 ///
 /// The `AuthStore` struct holds the authenticated user's record and a token
 /// used for making authenticated requests to the `PocketBase` API.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AuthStore {
     /// The authenticated user's record.
     pub record: AuthStoreRecord,
@@ -23,7 +42,7 @@ pub struct AuthStore {
 /// The `AuthStoreRecord` struct contains information about the user,
 /// such as their ID, email, etc. and other metadata related to the
 /// collection they belong to.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthStoreRecord {
     /// The user's unique ID.
@@ -43,3 +62,46 @@ pub struct AuthStoreRecord {
     /// Indicates whether the user's email has been verified.
     pub verified: bool,
 }
+
+impl AuthStore {
+    /// Returns `true` if the authenticated record is a superuser,
+    /// that is if it belongs to the `_superusers` system collection.
+    ///
+    /// This is checked locally, from the [`AuthStoreRecord::collection_name`], and can be used
+    /// to fail fast before calling admin-only APIs.
+    #[must_use]
+    pub fn is_superuser(&self) -> bool {
+        self.record.collection_name == SUPERUSERS_COLLECTION
+    }
+
+    /// Returns the expiration date of the token, read from its `exp` claim.
+    ///
+    /// Returns `None` if the token is not a valid JWT.
+    #[must_use]
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        let payload = self.token.split('.').nth(1)?;
+        let payload = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+        let claims = serde_json::from_slice::<TokenClaims>(&payload).ok()?;
+
+        DateTime::from_timestamp(claims.exp, 0)
+    }
+}
+
+/// The claims of an auth token used by this crate.
+#[derive(Deserialize)]
+struct TokenClaims {
+    /// Expiration date, as a UNIX timestamp.
+    exp: i64,
+}
+
+/// Body of the [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") response
+/// returned after a successful first authentication factor, when multi-factor authentication is enabled.
+///
+/// ```json
+/// { "mfaId": "..." }
+/// ```
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaRequiredResponse {
+    pub mfa_id: String,
+}
@@ -0,0 +1,151 @@
+use serde::de::IgnoredAny;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::error::{Error, ErrorKind};
+use crate::Collection;
+
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EmailChangeRequestBody<'a> {
+    new_email: &'a str,
+}
+
+/// Represents the various errors that can be obtained after a `request_email_change` request.
+#[derive(Error, Debug)]
+pub enum RequestEmailChangeError {
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
+    ///
+    /// The new email is empty, invalid or already in use.
+    #[error("One or more fields were not validated : {:?}", .0.data())]
+    BadRequest(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") HTTP error response.
+    ///
+    /// The client is not authenticated.
+    #[error("The client must be authenticated to request an email change.")]
+    Unauthorized(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
+    ///
+    /// The authenticated record doesn't belong to this collection.
+    #[error("The authenticated record is not allowed to perform this action.")]
+    Forbidden(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
+    ///
+    /// The collection doesn't exist or is not an auth collection.
+    #[error("The collection doesn't exist or is not an auth collection.")]
+    NotFound(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [429 Too Many Requests]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/429") HTTP error response.
+    ///
+    /// The server is rate limiting requests. Wait before retrying.
+    #[error("Too many requests. Please wait before retrying.")]
+    TooManyRequests(Error),
+    /// Communication with the `PocketBase` API failed.
+    ///
+    /// This could be caused by an internet outage, an error in the link given to the `PocketBase` SDK
+    /// and similar errors.
+    #[error("The communication with the PocketBase API failed: {0}")]
+    Unreachable(Error),
+    /// The response could not be parsed into the expected data structure.
+    #[error("Could not parse response into the expected data structure: {0}")]
+    ParseError(Error),
+    /// An unexpected error occurred.
+    /// The response from the `PocketBase` instance API was unexpected.
+    /// If you think its an error, please [open an issue on GitHub]("https://github.com/fromhorizons/pocketbase-rs/issues").
+    #[error("An unhandled status code was returned by the PocketBase API: {0}")]
+    UnexpectedResponse(Error),
+}
+
+impl From<Error> for RequestEmailChangeError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            ErrorKind::BadRequest => Self::BadRequest(error),
+            ErrorKind::Unauthorized => Self::Unauthorized(error),
+            ErrorKind::Forbidden => Self::Forbidden(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::TooManyRequests => Self::TooManyRequests(error),
+            ErrorKind::Unreachable => Self::Unreachable(error),
+            ErrorKind::ParseError => Self::ParseError(error),
+            _ => Self::UnexpectedResponse(error),
+        }
+    }
+}
+
+impl Collection<'_> {
+    /// Sends an email change request to the new address of the authenticated user.
+    ///
+    /// The client must be authenticated with a record of this collection. The email address
+    /// is only changed once confirmed with [`Collection::confirm_email_change`].
+    ///
+    /// # Parameters
+    ///
+    /// * `new_email`: The new email address of the authenticated user.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use pocketbase_rs::PocketBase;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     // ...
+    ///
+    ///     pb.collection("users")?
+    ///         .request_email_change("new@domain.com")
+    ///         .await?;
+    ///
+    ///     println!("The email change request was sent successfully.");
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the server fails (`RequestEmailChangeError::Unreachable`).
+    /// - The new email is refused (`RequestEmailChangeError::BadRequest`).
+    /// - The client is not authenticated (`RequestEmailChangeError::Unauthorized`).
+    /// - The authenticated record belongs to another collection (`RequestEmailChangeError::Forbidden`).
+    /// - The collection is not found (`RequestEmailChangeError::NotFound`).
+    /// - The request was rate limited (`RequestEmailChangeError::TooManyRequests`).
+    /// - The server responds with an unexpected status (`RequestEmailChangeError::UnexpectedResponse`).
+    /// - The response could not be parsed (`RequestEmailChangeError::ParseError`).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "request_email_change",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn request_email_change(
+        &self,
+        new_email: &str,
+    ) -> Result<(), RequestEmailChangeError> {
+        let endpoint = format!(
+            "{}/api/collections/{}/request-email-change",
+            self.client.base_url, self.name
+        );
+
+        let body = EmailChangeRequestBody { new_email };
+
+        self.client
+            .send_and_decode::<IgnoredAny>(self.client.request_post_json(&endpoint, &body))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::error::{Error, ErrorKind};
+use crate::Collection;
+
+#[derive(Clone, Default, Serialize)]
+struct OtpRequestBody<'a> {
+    email: &'a str,
+}
+
+/// The identifier of a one-time password sent by email.
+///
+/// Returned by [`Collection::request_otp`], to be used with [`Collection::auth_with_otp`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtpRequest {
+    /// Id of the one-time password request.
+    pub otp_id: String,
+}
+
+/// Represents the various errors that can be obtained after a `request_otp` request.
+#[derive(Error, Debug)]
+pub enum RequestOtpError {
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
+    ///
+    /// The given email is empty or is not a valid email address.
+    #[error("One or more fields were not validated : {:?}", .0.data())]
+    BadRequest(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
+    ///
+    /// One-time password authentication is not enabled for this collection.
+    #[error("One-time password authentication is not enabled for this collection.")]
+    Forbidden(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
+    ///
+    /// The collection doesn't exist or is not an auth collection.
+    #[error("The collection doesn't exist or is not an auth collection.")]
+    NotFound(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [429 Too Many Requests]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/429") HTTP error response.
+    ///
+    /// Too many one-time passwords were requested. Wait before retrying.
+    #[error("Too many one-time passwords were requested. Please wait before retrying.")]
+    TooManyRequests(Error),
+    /// Communication with the `PocketBase` API failed.
+    ///
+    /// This could be caused by an internet outage, an error in the link given to the `PocketBase` SDK
+    /// and similar errors.
+    #[error("The communication with the PocketBase API failed: {0}")]
+    Unreachable(Error),
+    /// The response could not be parsed into the expected data structure.
+    #[error("Could not parse response into the expected data structure: {0}")]
+    ParseError(Error),
+    /// An unexpected error occurred.
+    /// The response from the `PocketBase` instance API was unexpected.
+    /// If you think its an error, please [open an issue on GitHub]("https://github.com/fromhorizons/pocketbase-rs/issues").
+    #[error("An unhandled status code was returned by the PocketBase API: {0}")]
+    UnexpectedResponse(Error),
+}
+
+impl From<Error> for RequestOtpError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            ErrorKind::BadRequest => Self::BadRequest(error),
+            ErrorKind::Forbidden => Self::Forbidden(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::TooManyRequests => Self::TooManyRequests(error),
+            ErrorKind::Unreachable => Self::Unreachable(error),
+            ErrorKind::ParseError => Self::ParseError(error),
+            _ => Self::UnexpectedResponse(error),
+        }
+    }
+}
+
+impl Collection<'_> {
+    /// Sends a one-time password to the given email address.
+    ///
+    /// The returned [`OtpRequest::otp_id`] must then be passed to [`Collection::auth_with_otp`],
+    /// along with the password received by email.
+    ///
+    /// For security reasons, `PocketBase` returns an `otp_id` even when no record matches
+    /// the given email address.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use pocketbase_rs::PocketBase;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     let otp_request = pb.collection("users")?.request_otp("test@domain.com").await?;
+    ///
+    ///     // ...
+    ///
+    ///     let auth_data = pb
+    ///         .collection("users")?
+    ///         .auth_with_otp(&otp_request.otp_id, &password_received_by_email)
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The request to the server fails (`RequestOtpError::Unreachable`).
+    /// - The email is empty or invalid (`RequestOtpError::BadRequest`).
+    /// - One-time password authentication is disabled (`RequestOtpError::Forbidden`).
+    /// - The collection is not found (`RequestOtpError::NotFound`).
+    /// - The request was rate limited (`RequestOtpError::TooManyRequests`).
+    /// - The server responds with an unexpected status (`RequestOtpError::UnexpectedResponse`).
+    /// - The response could not be parsed into the expected data structure (`RequestOtpError::ParseError`).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "request_otp",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn request_otp(&self, email: &str) -> Result<OtpRequest, RequestOtpError> {
+        let endpoint = format!(
+            "{}/api/collections/{}/request-otp",
+            self.client.base_url, self.name
+        );
+
+        let otp_request = self
+            .client
+            .send_and_decode_without_refresh::<OtpRequest>(
+                self.client
+                    .request_post_json(&endpoint, &OtpRequestBody { email }),
+            )
+            .await?;
+
+        Ok(otp_request)
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use serde::de::IgnoredAny;
+
 use crate::error::RequestError;
 use crate::Collection;
 
@@ -21,7 +23,11 @@ impl<'a> Collection<'a> {
     /// This function may return:
     /// - `RequestError::Forbidden` if the operation is not permitted.
     /// - `RequestError::NotFound` if the method is not available for the given collection. You probably made a mistake in the collection name, or the collection is not of type "Auth collection".
-    /// - `RequestError::Unhandled` for all other error cases.
+    /// - `RequestError::BadRequest` if the request was refused, with the message of `PocketBase`.
+    /// - `RequestError::TooManyRequests` if the request was rate limited.
+    /// - `RequestError::Unreachable` if the request to the server fails.
+    /// - `RequestError::ParseError` if the response could not be parsed into the expected data structure.
+    /// - `RequestError::Unhandled` for all other error cases, including server errors.
     ///
     /// # Example
     ///
@@ -33,12 +39,12 @@ impl<'a> Collection<'a> {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let mut pb = PocketBase::new("http://localhost:8090");
+    ///     let pb = PocketBase::new("http://localhost:8090");
     ///
     ///     // ...
     ///
     ///     let refreshed_auth = pb
-    ///         .collection("users")
+    ///         .collection("users")?
     ///         .request_verification("user@domain.com")
     ///         .await?;
     ///
@@ -47,6 +53,18 @@ impl<'a> Collection<'a> {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "request_verification",
+            skip_all,
+            fields(
+                collection = %self.name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn request_verification(&self, email: &'a str) -> Result<(), RequestError> {
         let url = format!(
             "{}/api/collections/{}/request-verification",
@@ -55,33 +73,10 @@ impl<'a> Collection<'a> {
 
         let email: HashMap<String, String> = HashMap::from([("email".to_string(), email.into())]);
 
-        let request = (self.client.request_post_json(&url, &email)).send().await;
-
-        match request {
-            Ok(response) => match response.status() {
-                reqwest::StatusCode::NO_CONTENT => Ok(()),
-                reqwest::StatusCode::BAD_REQUEST => Err(RequestError::BadRequest(String::new())),
-                reqwest::StatusCode::NOT_FOUND => Err(RequestError::NotFound),
-                _ => Err(RequestError::Unhandled),
-            },
-            Err(error) => {
-                if let Some(error_status) = error.status() {
-                    match error_status {
-                        reqwest::StatusCode::UNAUTHORIZED => {
-                            return Err(RequestError::Unauthorized)
-                        }
-                        reqwest::StatusCode::FORBIDDEN => {
-                            return Err(RequestError::Forbidden);
-                        }
-                        reqwest::StatusCode::NOT_FOUND => {
-                            return Err(RequestError::NotFound);
-                        }
-                        _ => return Err(RequestError::Unhandled),
-                    }
-                }
+        self.client
+            .send_and_decode::<IgnoredAny>(self.client.request_post_json(&url, &email))
+            .await?;
 
-                Err(RequestError::Unhandled)
-            }
-        }
+        Ok(())
     }
 }
@@ -1,8 +1,11 @@
-use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+use serde::{de::DeserializeOwned, Serialize};
 use thiserror::Error;
 
-use crate::error::{BadRequestError, BadRequestResponse};
-use crate::Collection;
+use crate::error::{Error, ErrorKind};
+use crate::expand::validate_expand_depth;
+use crate::{Collection, Form, PocketBase};
 
 /// Represents the various errors that can be obtained after a `create` request.
 #[derive(Error, Debug)]
@@ -11,59 +14,96 @@ pub enum CreateError {
     /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
     ///
     /// One or more fields were not validated `PocketBase`.
-    #[error("One or more fields were not validated : {0:?}")]
-    BadRequest(Vec<BadRequestError>),
+    #[error("One or more fields were not validated : {:?}", .0.data())]
+    BadRequest(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") HTTP error response.
+    ///
+    /// The request requires a valid authorization token.
+    #[error("The request requires a valid authorization token.")]
+    Unauthorized(Error),
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
     ///
     /// The authorized record is not allowed to perform this action.
     #[error("The authorized record is not allowed to perform this action.")]
-    Forbidden,
+    Forbidden(Error),
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
     ///
     /// The requested resource wasn't found. Missing collection context.
     #[error("The requested resource wasn't found. Missing collection context.")]
-    NotFound,
+    NotFound(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [429 Too Many Requests]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/429") HTTP error response.
+    ///
+    /// The server is rate limiting requests. Wait before retrying.
+    #[error("Too many requests. Please wait before retrying.")]
+    TooManyRequests(Error),
     /// Communication with the `PocketBase` API failed.
     ///
     /// This could be caused by an internet outage, an error in the link given to the `PocketBase` SDK
     /// and similar errors.
     #[error("The communication with the PocketBase API failed: {0}")]
-    Unreachable(String),
+    Unreachable(Error),
     /// The response could not be parsed into the expected data structure.
     #[error("Could not parse response into the expected data structure. It usually means that there is a mismatch between the provided Generic Type Parameter and your Collection definition: {0}")]
-    ParseError(String),
+    ParseError(Error),
     /// An unexpected error occurred.
     /// The response from the `PocketBase` instance API was unexpected.
     /// If you think its an error, please [open an issue on GitHub]("https://github.com/fromhorizons/pocketbase-rs/issues").
     #[error("An unhandled status code was returned by the PocketBase API: {0}")]
-    UnexpectedResponse(String),
+    UnexpectedResponse(Error),
 }
 
-#[derive(Deserialize, Clone, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct CreateResponse {
-    pub collection_name: String,
-    pub collection_id: String,
-    pub id: String,
-    pub updated: String,
-    pub created: String,
+impl From<Error> for CreateError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            ErrorKind::BadRequest | ErrorKind::InvalidInput => Self::BadRequest(error),
+            ErrorKind::Unauthorized => Self::Unauthorized(error),
+            ErrorKind::Forbidden => Self::Forbidden(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::TooManyRequests => Self::TooManyRequests(error),
+            ErrorKind::Unreachable => Self::Unreachable(error),
+            ErrorKind::ParseError => Self::ParseError(error),
+            _ => Self::UnexpectedResponse(error),
+        }
+    }
 }
 
-impl Collection<'_> {
+/// The body of a record sent by the create and update builders.
+pub enum RecordBody<T> {
+    /// The fields of the record, sent as JSON.
+    Json(T),
+    /// The fields and the files of the record, sent as a multipart form.
+    Multipart(Form),
+}
+
+pub struct CollectionCreateBuilder<'a, T: Default + Serialize + Clone + Send> {
+    client: &'a PocketBase,
+    collection_name: Cow<'a, str>,
+    body: RecordBody<T>,
+    expand: Option<Cow<'a, str>>,
+    fields: Option<Cow<'a, str>>,
+}
+
+impl<'a> Collection<'a> {
     /// Create a new record in the given collection, from the given struct.
     ///
     /// If you need to upload files, you may want [`Collection::create_multipart()`].
     ///
     /// The `record` parameter must implement the `Serialize` trait.
     ///
+    /// This function returns a `CollectionCreateBuilder`, which allows you to expand the
+    /// relations or select the fields of the created record before calling `.call().await` to
+    /// execute the request.
+    ///
     /// # Example
     ///
     /// ```rust,ignore
     /// use std::error::Error;
     ///
-    /// use pocketbase_rs::PocketBase;
+    /// use pocketbase_rs::{PocketBase, Record};
     /// use serde::{Deserialize, Serialize};
     ///
     /// #[derive(Default, Serialize, Deserialize, Clone, Debug)]
@@ -74,70 +114,63 @@ impl Collection<'_> {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let mut pb = PocketBase::new("http://localhost:8090");
+    ///     let pb = PocketBase::new("http://localhost:8090");
     ///
     ///     // ...
     ///
     ///     let article = pb
-    ///         .collection("articles")
+    ///         .collection("articles")?
     ///         .create::<Article>(Article {
     ///             name: "test".to_string(),
     ///             content: "an interesting article content.".to_string(),
     ///         })
+    ///         .call::<Record<Article>>()
     ///         .await?;
     ///
+    ///     println!("Created article {} at {}", article.id, article.created);
+    ///
     ///     Ok(())
     /// }
     /// ```
-    ///
-    /// # Errors
-    ///
-    /// This function will return an error if:
-    /// - The request to the server fails (`CreateError::Unreachable`).
-    /// - The server responds with a bad request status (`CreateError::BadRequest`).
-    /// - The server responds with a forbidden status (`CreateError::Forbidden`).
-    /// - The record is not found (`CreateError::NotFound`).
-    /// - The server responds with an unexpected status (`CreateError::UnexpectedResponse`).
-    /// - The response could not be parsed into the expected data structure (`CreateError::ParseError`).
-    pub async fn create<T: Default + Serialize + Clone + Send>(
+    #[must_use]
+    pub fn create<T: Default + Serialize + Clone + Send>(
         self,
         record: T,
-    ) -> Result<CreateResponse, CreateError> {
-        let endpoint = format!(
-            "{}/api/collections/{}/records",
-            self.client.base_url, self.name
-        );
-
-        let request = self
-            .client
-            .request_post_json(&endpoint, &record)
-            .send()
-            .await;
-
-        create_processing(request).await
+    ) -> CollectionCreateBuilder<'a, T> {
+        CollectionCreateBuilder {
+            client: self.client,
+            collection_name: self.name,
+            body: RecordBody::Json(record),
+            expand: None,
+            fields: None,
+        }
     }
 
     /// Create a new record in the given collection, from the given [`crate::Form`].
     ///
     /// If you don't need to upload files, you probably want the "simpler" [`Collection::create()`] method.
     ///
+    /// This function returns a `CollectionCreateBuilder`, which allows you to expand the
+    /// relations or select the fields of the created record before calling `.call().await` to
+    /// execute the request.
+    ///
     /// # Example
     ///
     /// ```rust,ignore
     /// use std::{error::Error, fs};
     ///
-    /// use pocketbase_rs::{Form, Part, PocketBaseAdminBuilder};
+    /// use pocketbase_rs::{Form, Part, PocketBaseAdminBuilder, Record};
     /// use serde::{Deserialize, Serialize};
     ///
     /// #[derive(Default, Serialize, Deserialize, Clone, Debug)]
-    /// pub struct Article {
+    /// pub struct Fox {
     ///     name: String,
-    ///     content: String,
+    ///     illustration: String,
     /// }
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let mut admin_pb = PocketBaseAdminBuilder::new("http://localhost:8081")
+    ///     let admin_pb = PocketBaseAdminBuilder::new("http://localhost:8081")
     ///         .auth_with_password("test@test.com", "abcdefghijkl")
     ///         .await?;
     ///
@@ -152,8 +185,9 @@ impl Collection<'_> {
     ///         .part("illustration", image_part);
     ///
     ///     let request = admin_pb
-    ///         .collection("foxes")
+    ///         .collection("foxes")?
     ///         .create_multipart(form)
+    ///         .call::<Record<Fox>>()
     ///         .await;
     ///
     ///     match request {
@@ -164,76 +198,113 @@ impl Collection<'_> {
     ///     Ok(())
     /// }
     /// ```
+    #[must_use]
+    pub fn create_multipart(self, form: Form) -> CollectionCreateBuilder<'a, ()> {
+        CollectionCreateBuilder {
+            client: self.client,
+            collection_name: self.name,
+            body: RecordBody::Multipart(form),
+            expand: None,
+            fields: None,
+        }
+    }
+}
+
+impl<'a, T: Default + Serialize + Clone + Send> CollectionCreateBuilder<'a, T> {
+    /// Auto expand the relations of the created record.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?expand=relField1,relField2.subRelField
+    /// ```
+    ///
+    /// A [`crate::Expand`] builds the relations to expand, and can be given instead of a string.
+    /// A relation nested deeper than 6 levels is rejected with a [`CreateError::BadRequest`],
+    /// without sending the request.
+    pub fn expand(mut self, expand: impl Into<Cow<'a, str>>) -> Self {
+        self.expand = Some(expand.into());
+        self
+    }
+
+    /// Comma separated string of the fields of the created record to return in the JSON response
+    /// *(by default returns all fields)*.
+    ///
+    /// A [`crate::Fields`] builds the list, and can be given instead of a string.
+    pub fn fields(mut self, fields: impl Into<Cow<'a, str>>) -> Self {
+        self.fields = Some(fields.into());
+        self
+    }
+
+    /// Sends the request and returns the created record.
+    ///
+    /// `PocketBase` returns the whole record, with its computed fields and autodates.
+    /// It is deserialized into `R`, for example the struct of the record, or a
+    /// [`crate::Record`] to also read its system fields *(`Record<()>` for only them)*.
     ///
     /// # Errors
     ///
     /// This function will return an error if:
     /// - The request to the server fails (`CreateError::Unreachable`).
-    /// - The server responds with a bad request status (`CreateError::BadRequest`).
+    /// - The server responds with a bad request status, or the expanded relations are nested too
+    ///   deeply (`CreateError::BadRequest`).
+    /// - The request requires a valid authorization token (`CreateError::Unauthorized`).
     /// - The server responds with a forbidden status (`CreateError::Forbidden`).
     /// - The record is not found (`CreateError::NotFound`).
+    /// - The request was rate limited (`CreateError::TooManyRequests`).
     /// - The server responds with an unexpected status (`CreateError::UnexpectedResponse`).
     /// - The response could not be parsed into the expected data structure (`CreateError::ParseError`).
-    pub async fn create_multipart(
-        self,
-        form: reqwest::multipart::Form,
-    ) -> Result<CreateResponse, CreateError> {
-        let collection_name = self.name;
-
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "create",
+            skip_all,
+            fields(
+                collection = %self.collection_name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn call<R: DeserializeOwned>(self) -> Result<R, CreateError> {
         let endpoint = format!(
             "{}/api/collections/{}/records",
-            self.client.base_url, collection_name
+            self.client.base_url, self.collection_name
         );
 
-        let request = self.client.request_post_form(&endpoint, form).send().await;
+        let request = match self.body {
+            RecordBody::Json(record) => self.client.request_post_json(&endpoint, &record),
+            RecordBody::Multipart(form) => self.client.request_post_form(&endpoint, form),
+        };
+
+        let query_parameters = record_query(self.expand.as_deref(), self.fields.as_deref())?;
 
-        create_processing(request).await
+        let record = self
+            .client
+            .send_and_decode::<R>(request.query(&query_parameters))
+            .await?;
+
+        Ok(record)
     }
 }
 
-async fn create_processing(
-    request: Result<reqwest::Response, reqwest::Error>,
-) -> Result<CreateResponse, CreateError> {
-    match request {
-        Ok(response) => match response.status() {
-            reqwest::StatusCode::OK => {
-                let data = response.json::<CreateResponse>().await;
-
-                match data {
-                    Ok(data) => Ok(data),
-                    Err(error) => Err(CreateError::ParseError(error.to_string())),
-                }
-            }
-
-            reqwest::StatusCode::BAD_REQUEST => {
-                let data = response.json::<BadRequestResponse>().await;
-
-                match data {
-                    Ok(bad_response) => {
-                        let mut errors: Vec<BadRequestError> = vec![];
-
-                        for (error_name, error_data) in bad_response.data {
-                            errors.push(BadRequestError {
-                                name: error_name,
-                                code: error_data.code,
-                                message: error_data.message,
-                            });
-                        }
-
-                        Err(CreateError::BadRequest(errors))
-                    }
-                    Err(error) => Err(CreateError::ParseError(error.to_string())),
-                }
-            }
-
-            reqwest::StatusCode::FORBIDDEN => Err(CreateError::Forbidden),
-            reqwest::StatusCode::NOT_FOUND => Err(CreateError::NotFound),
-
-            _ => Err(CreateError::UnexpectedResponse(
-                response.status().to_string(),
-            )),
-        },
-
-        Err(error) => Err(CreateError::Unreachable(error.to_string())),
+/// Builds the query of a create or update request, returning the record with the given
+/// expanded relations and fields.
+///
+/// Rejects an expand parameter nested too deeply, see [`validate_expand_depth`].
+pub fn record_query<'q>(
+    expand: Option<&'q str>,
+    fields: Option<&'q str>,
+) -> Result<Vec<(&'q str, &'q str)>, Error> {
+    let mut query_parameters = vec![];
+
+    if let Some(expand) = expand {
+        validate_expand_depth(expand)?;
+        query_parameters.push(("expand", expand));
     }
+
+    if let Some(fields) = fields.filter(|fields| !fields.is_empty()) {
+        query_parameters.push(("fields", fields));
+    }
+
+    Ok(query_parameters)
 }
@@ -1,18 +1,62 @@
-use crate::Collection;
+use serde::de::IgnoredAny;
 use thiserror::Error;
 
+use crate::error::{Error, ErrorKind};
+use crate::Collection;
+
+/// Represents the various errors that can be obtained after a `delete` request.
 #[derive(Error, Debug)]
 pub enum DeleteError {
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
+    ///
+    /// The record may be referenced by a required relation of another record. Also returned when the record id is empty.
     #[error("Failed to delete record. Make sure that the record is not part of a required relation reference.")]
-    BadRequest,
+    BadRequest(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") HTTP error response.
+    ///
+    /// The request requires a valid authorization token.
+    #[error("The request requires a valid authorization token.")]
+    Unauthorized(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
     #[error("Only superusers can access this action.")]
-    Forbidden,
+    Forbidden(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
     #[error("The requested resource wasn't found.")]
-    NotFound,
+    NotFound(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [429 Too Many Requests]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/429") HTTP error response.
+    ///
+    /// The server is rate limiting requests. Wait before retrying.
+    #[error("Too many requests. Please wait before retrying.")]
+    TooManyRequests(Error),
+    /// Communication with the `PocketBase` API failed.
+    ///
+    /// This could be caused by an internet outage, an error in the link given to the `PocketBase` SDK
+    /// and similar errors.
     #[error("The communication with the PocketBase API failed: {0}")]
-    Unreachable(String),
+    Unreachable(Error),
+    /// The response from the `PocketBase` instance API was unexpected.
+    /// If you think its an error, please [open an issue on GitHub]("https://github.com/fromhorizons/pocketbase-rs/issues").
     #[error("An unhandled status code was returned by the PocketBase API: {0}")]
-    UnexpectedResponse(String),
+    UnexpectedResponse(Error),
+}
+
+impl From<Error> for DeleteError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            ErrorKind::BadRequest => Self::BadRequest(error),
+            ErrorKind::Unauthorized => Self::Unauthorized(error),
+            ErrorKind::Forbidden => Self::Forbidden(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::TooManyRequests => Self::TooManyRequests(error),
+            ErrorKind::Unreachable => Self::Unreachable(error),
+            _ => Self::UnexpectedResponse(error),
+        }
+    }
 }
 
 impl<'a> Collection<'a> {
@@ -32,46 +76,42 @@ impl<'a> Collection<'a> {
     ///
     /// This function will return an error if:
     /// * The server responds with a bad request status (`DeleteError::BadRequest`).
+    /// * The request requires a valid authorization token (`DeleteError::Unauthorized`).
     /// * The server responds with a forbidden status (`DeleteError::Forbidden`).
     /// * The record is not found (`DeleteError::NotFound`).
+    /// * The request was rate limited (`DeleteError::TooManyRequests`).
     /// * The request to the server fails (`DeleteError::Unreachable`).
     /// * The server responds with an unexpected status (`DeleteError::UnexpectedResponse`).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "delete",
+            skip_all,
+            fields(
+                collection = %self.name,
+                record_id = %record_id,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn delete(&self, record_id: &'a str) -> Result<(), DeleteError> {
         // Validate record_id
         if record_id.is_empty() {
-            return Err(DeleteError::BadRequest);
+            return Err(DeleteError::BadRequest(Error::new(
+                ErrorKind::InvalidInput,
+                "The record id cannot be empty.",
+            )));
         }
 
         let endpoint = format!(
             "{}/api/collections/{}/records/{}",
             self.client.base_url, self.name, record_id
         );
-        let request = self.client.request_delete(&endpoint).send().await;
+        self.client
+            .send_and_decode::<IgnoredAny>(self.client.request_delete(&endpoint))
+            .await?;
 
-        match request {
-            Ok(response) => match response.status() {
-                reqwest::StatusCode::NO_CONTENT | reqwest::StatusCode::OK => Ok(()),
-                reqwest::StatusCode::BAD_REQUEST => Err(DeleteError::BadRequest),
-                reqwest::StatusCode::FORBIDDEN => Err(DeleteError::Forbidden),
-                reqwest::StatusCode::NOT_FOUND => Err(DeleteError::NotFound),
-                _ => Err(DeleteError::UnexpectedResponse(format!(
-                    "Status: {}, Collection: {}, Record: {}",
-                    response.status(),
-                    self.name,
-                    record_id
-                ))),
-            },
-            Err(e) => {
-                if e.is_timeout() {
-                    Err(DeleteError::Unreachable("Request timed out".to_string()))
-                } else if e.is_connect() {
-                    Err(DeleteError::Unreachable(
-                        "Failed to connect to server".to_string(),
-                    ))
-                } else {
-                    Err(DeleteError::Unreachable(e.to_string()))
-                }
-            }
-        }
+        Ok(())
     }
 }
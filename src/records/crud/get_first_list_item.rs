@@ -1,17 +1,22 @@
 // set SkipTotal to true for performances
 
+use std::borrow::Cow;
+
 use serde::{de::DeserializeOwned, Deserialize};
 
-use crate::error::RequestError;
+use crate::error::{Error, ErrorKind, RequestError};
+use crate::expand::validate_expand_depth;
+use crate::filter::FilterExpression;
 use crate::PocketBase;
 use crate::{Collection, RecordList};
 
 pub struct CollectionGetFirstListItemBuilder<'a, T: Send + Deserialize<'a>> {
     client: &'a PocketBase,
-    collection_name: &'a str,
-    sort: Option<&'a str>,
-    expand: Option<&'a str>,
-    filter: Option<&'a str>,
+    collection_name: Cow<'a, str>,
+    sort: Option<Cow<'a, str>>,
+    expand: Option<Cow<'a, str>>,
+    fields: Option<Cow<'a, str>>,
+    filter: Option<FilterExpression<'a>>,
     _marker: std::marker::PhantomData<T>,
 }
 
@@ -40,12 +45,12 @@ impl<'a> Collection<'a> {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let mut pb = PocketBase::new("http://localhost:8090");
+    ///     let pb = PocketBase::new("http://localhost:8090");
     ///
     ///     // ...
     ///
     ///     let article = pb
-    ///         .collection("articles")
+    ///         .collection("articles")?
     ///         .get_first_list_item::<Article>()
     ///         .filter("language='en'")
     ///         .call()
@@ -57,7 +62,7 @@ impl<'a> Collection<'a> {
     /// }
     /// ```
     #[must_use]
-    pub const fn get_first_list_item<T: Default + DeserializeOwned + Clone + Send>(
+    pub fn get_first_list_item<T: Default + DeserializeOwned + Clone + Send>(
         self,
     ) -> CollectionGetFirstListItemBuilder<'a, T> {
         CollectionGetFirstListItemBuilder {
@@ -65,6 +70,7 @@ impl<'a> Collection<'a> {
             collection_name: self.name,
             sort: None,
             expand: None,
+            fields: None,
             filter: None,
             _marker: std::marker::PhantomData,
         }
@@ -79,8 +85,10 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetFirstListIte
     /// ```toml
     /// ?sort=-created,id # DESC by created and ASC by id
     /// ``````
-    pub const fn sort(mut self, sort: &'a str) -> Self {
-        self.sort = Some(sort);
+    ///
+    /// A [`crate::Sort`] builds the order, and can be given instead of a string.
+    pub fn sort(mut self, sort: impl Into<Cow<'a, str>>) -> Self {
+        self.sort = Some(sort.into());
         self
     }
 
@@ -113,8 +121,12 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetFirstListIte
     ///    - `?!~` *Any/At least one of* NOT Like/Contains (if not specified auto wraps the right string OPERAND in a "%" for wildcard match)
     ///
     /// To group and combine several expressions you could use brackets `(...)`, `&&` (AND) and `||` (OR) tokens.
-    pub const fn filter(mut self, filter: &'a str) -> Self {
-        self.filter = Some(filter);
+    ///
+    /// A [`crate::Filter`] builds the expression with its values quoted and escaped, and can be
+    /// given instead of a string. A value that can't be quoted is rejected with a
+    /// [`RequestError::BadRequest`], without sending the request.
+    pub fn filter(mut self, filter: impl Into<FilterExpression<'a>>) -> Self {
+        self.filter = Some(filter.into());
         self
     }
 
@@ -128,8 +140,32 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetFirstListIte
     /// Supports up to 6-levels depth nested relations expansion.
     /// The expanded relations will be appended to each individual record under the `expand` property (eg. `"expand": {"relField1": {...}, ...}`).
     /// Only the relations to which the request user has permissions to **view** will be expanded.
-    pub const fn expand(mut self, expand: &'a str) -> Self {
-        self.expand = Some(expand);
+    ///
+    /// A [`crate::Expand`] builds the relations to expand, and can be given instead of a string.
+    /// A relation nested deeper than 6 levels is rejected with a [`RequestError::BadRequest`],
+    /// without sending the request.
+    pub fn expand(mut self, expand: impl Into<Cow<'a, str>>) -> Self {
+        self.expand = Some(expand.into());
+        self
+    }
+
+    /// Comma separated string of the fields to return in the JSON response *(by default returns all fields)*.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?fields=*,expand.relField.name
+    /// ```
+    ///
+    /// `*` targets all keys from the specific depth level.
+    ///
+    /// The `:excerpt(maxLength, withEllipsis)` modifier returns the first `maxLength` characters
+    /// of a text field as plain text, ending with `...` if `withEllipsis` is `true`
+    /// *(example: `?fields=*,description:excerpt(200,true)`)*.
+    ///
+    /// A [`crate::Fields`] builds the list, and can be given instead of a string.
+    /// [`crate::Fields::of`] lists the fields of `T`, so that only them are downloaded.
+    pub fn fields(mut self, fields: impl Into<Cow<'a, str>>) -> Self {
+        self.fields = Some(fields.into());
         self
     }
 
@@ -138,7 +174,38 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetFirstListIte
     /// This method finalizes the request built using the builder pattern
     /// and sends it to the API endpoint. It should be called after all
     /// desired parameters and configurations have been set on the builder.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The filter can't be rendered, the server responds with a bad request status, or the
+    ///   expanded relations are nested too deeply (`RequestError::BadRequest`).
+    /// - The request requires a valid authorization token (`RequestError::Unauthorized`).
+    /// - The server responds with a forbidden status (`RequestError::Forbidden`).
+    /// - No record matches the filter, or the collection is not found (`RequestError::NotFound`).
+    /// - The request was rate limited (`RequestError::TooManyRequests`).
+    /// - The request to the server fails (`RequestError::Unreachable`).
+    /// - The response could not be parsed into the expected data structure (`RequestError::ParseError`).
+    /// - The server responds with an unexpected status (`RequestError::Unhandled`).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "get_first_list_item",
+            skip_all,
+            fields(
+                collection = %self.collection_name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn call(self) -> Result<T, RequestError> {
+        let filter = self
+            .filter
+            .map(FilterExpression::into_result)
+            .transpose()
+            .map_err(Error::from)?;
+
         let url = format!(
             "{}/api/collections/{}/records",
             self.client.base_url, self.collection_name
@@ -147,49 +214,35 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetFirstListIte
         let mut query_parameters: Vec<(&str, &str)> =
             vec![("page", "1"), ("perPage", "1"), ("skipTotal", "true")];
 
-        if let Some(sort) = self.sort {
+        if let Some(sort) = self.sort.as_deref() {
             query_parameters.push(("sort", sort));
         }
 
-        if let Some(filter) = self.filter {
+        if let Some(filter) = filter.as_deref() {
             query_parameters.push(("filter", filter));
         }
 
-        if let Some(expand) = self.expand {
+        if let Some(expand) = self.expand.as_deref() {
+            validate_expand_depth(expand)?;
             query_parameters.push(("expand", expand));
         }
 
-        let request = self
+        if let Some(fields) = self.fields.as_deref().filter(|fields| !fields.is_empty()) {
+            query_parameters.push(("fields", fields));
+        }
+
+        let records = self
             .client
-            .request_get(&url, Some(query_parameters))
-            .send()
-            .await;
-
-        let response = match request {
-            Ok(response) => response
-                .error_for_status()
-                .map_err(|err| match err.status() {
-                    Some(reqwest::StatusCode::FORBIDDEN) => RequestError::Forbidden,
-                    Some(reqwest::StatusCode::NOT_FOUND) => RequestError::NotFound,
-                    _ => RequestError::Unhandled,
-                })?,
-            Err(error) => {
-                return Err(match error.status() {
-                    Some(reqwest::StatusCode::FORBIDDEN) => RequestError::Forbidden,
-                    Some(reqwest::StatusCode::NOT_FOUND) => RequestError::NotFound,
-                    _ => RequestError::Unhandled,
-                });
-            }
-        };
-
-        // Parse JSON response
-        let records = response
-            .json::<RecordList<T>>()
-            .await
-            .map_err(|error| RequestError::ParseError(error.to_string()))?;
+            .send_and_decode::<RecordList<T>>(self.client.request_get(&url, Some(query_parameters)))
+            .await?;
 
         records.items.first().map_or_else(
-            || Err(RequestError::ParseError("No record found.".to_owned())),
+            || {
+                Err(RequestError::NotFound(Error::new(
+                    ErrorKind::NotFound,
+                    "No record matches the filter.",
+                )))
+            },
             |record| Ok(record.clone()),
         )
     }
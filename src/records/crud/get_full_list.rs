@@ -1 +1,369 @@
-// set skipTotal to true for performances reasons
\ No newline at end of file
+// perPage (batch) default: 500
+// skipTotal is always set, pagination stops on the first incomplete page,
+// or on the last page when totalPages is known
+
+use std::borrow::Cow;
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::error::{Error, RequestError};
+use crate::expand::validate_expand_depth;
+use crate::filter::FilterExpression;
+use crate::records::crud::get_list::fetch_record_list;
+use crate::{Collection, PocketBase};
+
+/// Default amount of records fetched per request by [`Collection::get_full_list`].
+const DEFAULT_BATCH_SIZE: u16 = 500;
+
+pub struct CollectionGetFullListBuilder<'a, T: Send + Deserialize<'a>> {
+    client: &'a PocketBase,
+    collection_name: Cow<'a, str>,
+    batch: u16,
+    sort: Option<Cow<'a, str>>,
+    expand: Option<Cow<'a, str>>,
+    filter: Option<FilterExpression<'a>>,
+    fields: Option<Cow<'a, str>>,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<'a> Collection<'a> {
+    /// Fetch all records matching the given options from the given collection.
+    ///
+    /// This function returns a `CollectionGetFullListBuilder`, which allows you to specify
+    /// additional options such as sorting, filtering or expanding linked records before calling
+    /// `.call().await` to execute the request.
+    ///
+    /// Records are requested page by page (see [`CollectionGetFullListBuilder::batch`]) until
+    /// every matching record has been retrieved.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use pocketbase_rs::PocketBase;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Default, Deserialize, Clone)]
+    /// struct Article {
+    ///     id: String,
+    ///     title: String,
+    ///     content: String,
+    ///     language: String
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     // ...
+    ///
+    ///     let articles = pb
+    ///         .collection("articles")?
+    ///         .get_full_list::<Article>()
+    ///         .filter("language='en'")
+    ///         .sort("-created")
+    ///         .batch(200)
+    ///         .call()
+    ///         .await?;
+    ///
+    ///     println!("Found {} articles", articles.len());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn get_full_list<T: Default + DeserializeOwned + Clone + Send>(
+        self,
+    ) -> CollectionGetFullListBuilder<'a, T> {
+        CollectionGetFullListBuilder {
+            client: self.client,
+            collection_name: self.name,
+            batch: DEFAULT_BATCH_SIZE,
+            sort: None,
+            expand: None,
+            filter: None,
+            fields: None,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetFullListBuilder<'a, T> {
+    /// Specify the amount of records requested per page (default to 500).
+    ///
+    /// The value is clamped between **1** and **500**, which is the maximum
+    /// amount of records `PocketBase` returns for a single page.
+    pub fn batch(mut self, size: u16) -> Self {
+        self.batch = size.clamp(1, DEFAULT_BATCH_SIZE);
+        self
+    }
+
+    /// Specify the records order attribute(s).
+    /// Add `-`/`+` (default) in front of the attribute for DESC / ASC order.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?sort=-created,id # DESC by created and ASC by id
+    /// ``````
+    ///
+    /// A [`crate::Sort`] builds the order, and can be given instead of a string.
+    pub fn sort(mut self, sort: impl Into<Cow<'a, str>>) -> Self {
+        self.sort = Some(sort.into());
+        self
+    }
+
+    /// Filter the returned records.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?filter=(id="abc" && created>'1970-01-01')
+    /// ```
+    ///
+    /// See `CollectionGetListBuilder::filter` for the list of supported operators.
+    ///
+    /// A [`crate::Filter`] builds the expression with its values quoted and escaped, and can be
+    /// given instead of a string. A value that can't be quoted is rejected with a
+    /// [`RequestError::BadRequest`], without sending the request.
+    pub fn filter(mut self, filter: impl Into<FilterExpression<'a>>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Auto expand record relations.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?expand=relField1,relField2.subRelField
+    /// ```
+    ///
+    /// Supports up to 6-levels depth nested relations expansion.
+    /// The expanded relations will be appended to each individual record under the `expand` property (eg. `"expand": {"relField1": {...}, ...}`).
+    /// Only the relations to which the request user has permissions to **view** will be expanded.
+    ///
+    /// A [`crate::Expand`] builds the relations to expand, and can be given instead of a string.
+    /// A relation nested deeper than 6 levels is rejected with a [`RequestError::BadRequest`],
+    /// without sending the request.
+    pub fn expand(mut self, expand: impl Into<Cow<'a, str>>) -> Self {
+        self.expand = Some(expand.into());
+        self
+    }
+
+    /// Comma separated string of the fields to return in the JSON response *(by default returns all fields)*.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?fields=*,expand.relField.name
+    /// ```
+    ///
+    /// `*` targets all keys from the specific depth level.
+    ///
+    /// The `:excerpt(maxLength, withEllipsis)` modifier returns the first `maxLength` characters
+    /// of a text field as plain text, ending with `...` if `withEllipsis` is `true`
+    /// *(example: `?fields=*,description:excerpt(200,true)`)*.
+    ///
+    /// A [`crate::Fields`] builds the list, and can be given instead of a string.
+    /// [`crate::Fields::of`] lists the fields of `T`, so that only them are downloaded.
+    pub fn fields(mut self, fields: impl Into<Cow<'a, str>>) -> Self {
+        self.fields = Some(fields.into());
+        self
+    }
+
+    /// Sends the requests and returns every matching record.
+    ///
+    /// This method finalizes the request built using the builder pattern
+    /// and walks through the pages of the collection until the last one is reached.
+    /// It should be called after all desired parameters and configurations have been set on the builder.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`RequestError`] encountered while fetching a page.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "get_full_list",
+            skip_all,
+            fields(
+                collection = %self.collection_name,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn call(self) -> Result<Vec<T>, RequestError> {
+        let filter = self
+            .filter
+            .map(FilterExpression::into_result)
+            .transpose()
+            .map_err(Error::from)?;
+
+        if let Some(expand) = self.expand.as_deref() {
+            validate_expand_depth(expand)?;
+        }
+
+        let per_page = self.batch.to_string();
+        let mut records: Vec<T> = vec![];
+        let mut page: u32 = 1;
+
+        loop {
+            let page_value = page.to_string();
+
+            let mut query_parameters: Vec<(&str, &str)> = vec![
+                ("page", &page_value),
+                ("perPage", &per_page),
+                ("skipTotal", "true"),
+            ];
+
+            if let Some(sort) = self.sort.as_deref() {
+                query_parameters.push(("sort", sort));
+            }
+
+            if let Some(filter) = filter.as_deref() {
+                query_parameters.push(("filter", filter));
+            }
+
+            if let Some(expand) = self.expand.as_deref() {
+                query_parameters.push(("expand", expand));
+            }
+
+            if let Some(fields) = self.fields.as_deref().filter(|fields| !fields.is_empty()) {
+                query_parameters.push(("fields", fields));
+            }
+
+            let list = fetch_record_list::<T>(self.client, &self.collection_name, query_parameters)
+                .await?;
+
+            // `totalPages` is `-1` with `skipTotal`, but a server may still send it.
+            let is_last_page = list.items.len() < usize::from(self.batch)
+                || (list.total_pages > 0 && list.page >= list.total_pages);
+
+            records.extend(list.items);
+
+            if is_last_page {
+                return Ok(records);
+            }
+
+            page += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use serde_json::{json, Value};
+
+    use crate::PocketBase;
+
+    /// A page of `count` records, with the `totalPages` sent by the server.
+    fn page(page: u32, count: usize, total_pages: i32) -> Value {
+        json!({
+            "page": page,
+            "perPage": 2,
+            "totalItems": -1,
+            "totalPages": total_pages,
+            "items": (0..count).map(|index| json!({ "id": format!("{page}-{index}") })).collect::<Vec<_>>(),
+        })
+    }
+
+    async fn ids(pb: &PocketBase) -> Vec<String> {
+        pb.collection("articles")
+            .unwrap()
+            .get_full_list::<Value>()
+            .batch(2)
+            .call()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|record| record["id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn stops_on_the_last_page() {
+        let server = MockServer::start_async().await;
+        for (number, total_pages) in [(1, 2), (2, 2)] {
+            server
+                .mock_async(|when, then| {
+                    when.path("/api/collections/articles/records")
+                        .query_param("page", number.to_string());
+                    then.status(200).json_body(page(number, 2, total_pages));
+                })
+                .await;
+        }
+        let third = server
+            .mock_async(|when, then| {
+                when.query_param("page", "3");
+                then.status(200).json_body(page(3, 0, 2));
+            })
+            .await;
+
+        let pb = PocketBase::new(&server.base_url());
+
+        assert_eq!(ids(&pb).await, ["1-0", "1-1", "2-0", "2-1"]);
+        assert_eq!(third.hits_async().await, 0);
+    }
+
+    #[tokio::test]
+    async fn stops_on_a_short_page() {
+        let server = MockServer::start_async().await;
+        for (number, count) in [(1, 2), (2, 1)] {
+            server
+                .mock_async(|when, then| {
+                    when.query_param("page", number.to_string());
+                    then.status(200).json_body(page(number, count, -1));
+                })
+                .await;
+        }
+
+        let pb = PocketBase::new(&server.base_url());
+
+        assert_eq!(ids(&pb).await, ["1-0", "1-1", "2-0"]);
+    }
+
+    #[tokio::test]
+    async fn stops_on_an_empty_page() {
+        let server = MockServer::start_async().await;
+        for (number, count) in [(1, 2), (2, 0)] {
+            server
+                .mock_async(|when, then| {
+                    when.query_param("page", number.to_string());
+                    then.status(200).json_body(page(number, count, -1));
+                })
+                .await;
+        }
+
+        let pb = PocketBase::new(&server.base_url());
+
+        assert_eq!(ids(&pb).await, ["1-0", "1-1"]);
+    }
+
+    #[tokio::test]
+    async fn forwards_the_filter_and_sort() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.query_param("filter", "language = 'en'")
+                    .query_param("sort", "-created")
+                    .query_param("perPage", "500")
+                    .query_param("skipTotal", "true");
+                then.status(200).json_body(page(1, 0, -1));
+            })
+            .await;
+
+        let pb = PocketBase::new(&server.base_url());
+
+        let records = pb
+            .collection("articles")
+            .unwrap()
+            .get_full_list::<Value>()
+            .filter("language = 'en'")
+            .sort("-created")
+            .call()
+            .await
+            .unwrap();
+
+        assert!(records.is_empty());
+        mock.assert_async().await;
+    }
+}
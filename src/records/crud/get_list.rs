@@ -2,21 +2,33 @@
 // perPage max:     500
 // filter, sort, expand, page, perPage, skipTotal
 
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
 use serde::{de::DeserializeOwned, Deserialize};
 
-use crate::error::RequestError;
+use crate::error::{Error, RequestError};
+use crate::expand::validate_expand_depth;
+use crate::filter::FilterExpression;
 use crate::PocketBase;
 use crate::{Collection, RecordList};
 
+#[derive(Clone)]
 pub struct CollectionGetListBuilder<'a, T: Send + Deserialize<'a>> {
     client: &'a PocketBase,
-    collection_name: &'a str,
+    collection_name: Cow<'a, str>,
     page: Option<String>,
     per_page: Option<String>,
-    sort: Option<&'a str>,
-    expand: Option<&'a str>,
-    filter: Option<&'a str>,
+    sort: Option<Cow<'a, str>>,
+    expand: Option<Cow<'a, str>>,
+    fields: Option<Cow<'a, str>>,
+    filter: Option<FilterExpression<'a>>,
     skip_total: bool,
+    prefetch: bool,
     _marker: std::marker::PhantomData<T>,
 }
 
@@ -44,12 +56,12 @@ impl<'a> Collection<'a> {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let mut pb = PocketBase::new("http://localhost:8090");
+    ///     let pb = PocketBase::new("http://localhost:8090");
     ///
     ///     // ...
     ///
     ///     let articles = pb
-    ///         .collection("articles")
+    ///         .collection("articles")?
     ///         .get_list::<Article>()
     ///         .sort("-created,id")
     ///         .call()
@@ -63,7 +75,7 @@ impl<'a> Collection<'a> {
     /// }
     /// ```
     #[must_use]
-    pub const fn get_list<T: Default + DeserializeOwned + Clone + Send>(
+    pub fn get_list<T: Default + DeserializeOwned + Clone + Send>(
         self,
     ) -> CollectionGetListBuilder<'a, T> {
         CollectionGetListBuilder {
@@ -73,8 +85,10 @@ impl<'a> Collection<'a> {
             per_page: None,
             sort: None,
             expand: None,
+            fields: None,
             filter: None,
             skip_total: false,
+            prefetch: false,
             _marker: std::marker::PhantomData,
         }
     }
@@ -103,8 +117,10 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetListBuilder<
     /// ```toml
     /// ?sort=-created,id # DESC by created and ASC by id
     /// ``````
-    pub const fn sort(mut self, sort: &'a str) -> Self {
-        self.sort = Some(sort);
+    ///
+    /// A [`crate::Sort`] builds the order, and can be given instead of a string.
+    pub fn sort(mut self, sort: impl Into<Cow<'a, str>>) -> Self {
+        self.sort = Some(sort.into());
         self
     }
 
@@ -137,8 +153,12 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetListBuilder<
     ///    - `?!~` *Any/At least one of* NOT Like/Contains (if not specified auto wraps the right string OPERAND in a "%" for wildcard match)
     ///
     /// To group and combine several expressions you could use brackets `(...)`, `&&` (AND) and `||` (OR) tokens.
-    pub const fn filter(mut self, filter: &'a str) -> Self {
-        self.filter = Some(filter);
+    ///
+    /// A [`crate::Filter`] builds the expression with its values quoted and escaped, and can be
+    /// given instead of a string. A value that can't be quoted is rejected with a
+    /// [`RequestError::BadRequest`], without sending the request.
+    pub fn filter(mut self, filter: impl Into<FilterExpression<'a>>) -> Self {
+        self.filter = Some(filter.into());
         self
     }
 
@@ -152,8 +172,32 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetListBuilder<
     /// Supports up to 6-levels depth nested relations expansion.
     /// The expanded relations will be appended to each individual record under the `expand` property (eg. `"expand": {"relField1": {...}, ...}`).
     /// Only the relations to which the request user has permissions to **view** will be expanded.
-    pub const fn expand(mut self, expand: &'a str) -> Self {
-        self.expand = Some(expand);
+    ///
+    /// A [`crate::Expand`] builds the relations to expand, and can be given instead of a string.
+    /// A relation nested deeper than 6 levels is rejected with a [`RequestError::BadRequest`],
+    /// without sending the request.
+    pub fn expand(mut self, expand: impl Into<Cow<'a, str>>) -> Self {
+        self.expand = Some(expand.into());
+        self
+    }
+
+    /// Comma separated string of the fields to return in the JSON response *(by default returns all fields)*.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?fields=*,expand.relField.name
+    /// ```
+    ///
+    /// `*` targets all keys from the specific depth level.
+    ///
+    /// The `:excerpt(maxLength, withEllipsis)` modifier returns the first `maxLength` characters
+    /// of a text field as plain text, ending with `...` if `withEllipsis` is `true`
+    /// *(example: `?fields=*,description:excerpt(200,true)`)*.
+    ///
+    /// A [`crate::Fields`] builds the list, and can be given instead of a string.
+    /// [`crate::Fields::of`] lists the fields of `T`, so that only them are downloaded.
+    pub fn fields(mut self, fields: impl Into<Cow<'a, str>>) -> Self {
+        self.fields = Some(fields.into());
         self
     }
 
@@ -165,16 +209,27 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetListBuilder<
         self
     }
 
+    /// When streaming the records with [`CollectionGetListBuilder::into_stream`], request
+    /// the next page as soon as the current one has been received *(default to false)*.
+    ///
+    /// This overlaps the network round-trip of the next page with the consumption of the
+    /// current one, at the cost of one request that may not be needed if the stream is dropped early.
+    pub const fn prefetch(mut self, prefetch: bool) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
     /// Sends the request and returns the response.
     ///
     /// This method finalizes the request built using the builder pattern
     /// and sends it to the API endpoint. It should be called after all
     /// desired parameters and configurations have been set on the builder.
     pub async fn call(self) -> Result<RecordList<T>, RequestError> {
-        let url = format!(
-            "{}/api/collections/{}/records",
-            self.client.base_url, self.collection_name
-        );
+        let filter = self
+            .filter
+            .map(FilterExpression::into_result)
+            .transpose()
+            .map_err(Error::from)?;
 
         let mut query_parameters: Vec<(&str, &str)> = vec![];
 
@@ -186,49 +241,327 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetListBuilder<
             query_parameters.push(("perPage", per_page));
         }
 
-        if let Some(sort) = self.sort {
+        if let Some(sort) = self.sort.as_deref() {
             query_parameters.push(("sort", sort));
         }
 
-        if let Some(filter) = self.filter {
+        if let Some(filter) = filter.as_deref() {
             query_parameters.push(("filter", filter));
         }
 
-        if let Some(expand) = self.expand {
+        if let Some(expand) = self.expand.as_deref() {
+            validate_expand_depth(expand)?;
             query_parameters.push(("expand", expand));
         }
 
-        let request = self
-            .client
-            .request_get(&url, Some(query_parameters))
-            .send()
-            .await;
+        if let Some(fields) = self.fields.as_deref().filter(|fields| !fields.is_empty()) {
+            query_parameters.push(("fields", fields));
+        }
+
+        if self.skip_total {
+            query_parameters.push(("skipTotal", "true"));
+        }
+
+        fetch_record_list(self.client, &self.collection_name, query_parameters).await
+    }
+}
+
+impl<'a, T: Default + DeserializeOwned + Clone + Send + 'a> CollectionGetListBuilder<'a, T> {
+    /// Turns the request into a [`Stream`] yielding every matching record, one at a time.
+    ///
+    /// Pages are fetched lazily, starting from the configured `page` *(default to 1)* and
+    /// using the configured `per_page` as page size. The stream ends once the last page
+    /// (according to `totalPages`) or an empty page has been reached, or after the first error.
+    ///
+    /// See [`CollectionGetListBuilder::prefetch`] to request the next page ahead of time.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::error::Error;
+    ///
+    /// use futures::StreamExt;
+    /// use pocketbase_rs::PocketBase;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Default, Deserialize, Clone, Debug)]
+    /// struct Article {
+    ///     id: String,
+    ///     title: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let pb = PocketBase::new("http://localhost:8090");
+    ///
+    ///     // ...
+    ///
+    ///     let mut articles = pb
+    ///         .collection("articles")?
+    ///         .get_list::<Article>()
+    ///         .per_page(500)
+    ///         .skip_total(true)
+    ///         .prefetch(true)
+    ///         .into_stream();
+    ///
+    ///     while let Some(article) = articles.next().await {
+    ///         println!("{:?}", article?);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn into_stream(self) -> RecordStream<'a, T> {
+        let next_page = self
+            .page
+            .as_deref()
+            .and_then(|page| page.parse().ok())
+            .unwrap_or(1);
+
+        RecordStream {
+            builder: self,
+            next_page,
+            finished: false,
+            buffer: VecDeque::new(),
+            pending: None,
+            fetched: None,
+        }
+    }
+}
+
+type PageFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<RecordList<T>, RequestError>> + Send + 'a>>;
 
-        let response = match request {
-            Ok(response) => response
-                .error_for_status()
-                .map_err(|err| match err.status() {
-                    Some(reqwest::StatusCode::FORBIDDEN) => RequestError::Forbidden,
-                    Some(reqwest::StatusCode::NOT_FOUND) => RequestError::NotFound,
-                    Some(reqwest::StatusCode::TOO_MANY_REQUESTS) => RequestError::TooManyRequests,
-                    _ => RequestError::Unhandled,
-                })?,
-            Err(error) => {
-                return Err(match error.status() {
-                    Some(reqwest::StatusCode::FORBIDDEN) => RequestError::Forbidden,
-                    Some(reqwest::StatusCode::NOT_FOUND) => RequestError::NotFound,
-                    Some(reqwest::StatusCode::TOO_MANY_REQUESTS) => RequestError::TooManyRequests,
-                    _ => RequestError::Unhandled,
-                });
+/// A [`Stream`] of records walking through the pages of a collection.
+///
+/// Created by [`CollectionGetListBuilder::into_stream`].
+pub struct RecordStream<'a, T: Send + Deserialize<'a>> {
+    builder: CollectionGetListBuilder<'a, T>,
+    next_page: u32,
+    finished: bool,
+    buffer: VecDeque<T>,
+    pending: Option<PageFuture<'a, T>>,
+    fetched: Option<Result<RecordList<T>, RequestError>>,
+}
+
+// Records are only ever moved in and out of the buffer, they are never pinned.
+impl<'a, T: Send + Deserialize<'a>> Unpin for RecordStream<'a, T> {}
+
+impl<'a, T: Default + DeserializeOwned + Clone + Send + 'a> RecordStream<'a, T> {
+    fn request_next_page(&mut self) {
+        let mut builder = self.builder.clone();
+        builder.page = Some(self.next_page.to_string());
+
+        self.next_page += 1;
+        self.pending = Some(Box::pin(builder.call()));
+    }
+
+    fn receive_page(&mut self, list: RecordList<T>) {
+        let is_last_page = list.items.is_empty()
+            || (list.total_pages >= 0 && list.page >= list.total_pages)
+            || usize::try_from(list.per_page).is_ok_and(|per_page| list.items.len() < per_page);
+
+        self.buffer.extend(list.items);
+
+        if is_last_page {
+            self.finished = true;
+        } else if self.builder.prefetch {
+            self.request_next_page();
+        }
+    }
+}
+
+impl<'a, T: Default + DeserializeOwned + Clone + Send + 'a> Stream for RecordStream<'a, T> {
+    type Item = Result<T, RequestError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // Keep driving the in-flight page request, even while buffered records remain.
+            if let Some(pending) = this.pending.as_mut() {
+                if let Poll::Ready(result) = pending.as_mut().poll(cx) {
+                    this.pending = None;
+                    this.fetched = Some(result);
+                }
+            }
+
+            if let Some(record) = this.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(record)));
+            }
+
+            match this.fetched.take() {
+                Some(Ok(list)) => {
+                    this.receive_page(list);
+                    continue;
+                }
+                Some(Err(error)) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(error)));
+                }
+                None => {}
+            }
+
+            if this.pending.is_some() {
+                return Poll::Pending;
+            }
+
+            if this.finished {
+                return Poll::Ready(None);
             }
-        };
 
-        // Parse JSON response
-        let records = response
-            .json::<RecordList<T>>()
+            this.request_next_page();
+        }
+    }
+}
+
+/// Fetches a single page of records from the given collection.
+///
+/// Shared by every builder walking the `records` list endpoint, so that they all
+/// map the `PocketBase` API responses the same way.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "get_list",
+        skip_all,
+        fields(
+            collection = %collection_name,
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        )
+    )
+)]
+pub async fn fetch_record_list<T: DeserializeOwned>(
+    client: &PocketBase,
+    collection_name: &str,
+    query_parameters: Vec<(&str, &str)>,
+) -> Result<RecordList<T>, RequestError> {
+    let url = format!(
+        "{}/api/collections/{}/records",
+        client.base_url, collection_name
+    );
+
+    let records = client
+        .send_and_decode::<RecordList<T>>(client.request_get(&url, Some(query_parameters)))
+        .await?;
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use httpmock::prelude::*;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// A page of `count` records out of `per_page`, with the `totalPages` sent by the server.
+    fn page(page: u32, count: usize, total_pages: i32) -> Value {
+        json!({
+            "page": page,
+            "perPage": 2,
+            "totalItems": -1,
+            "totalPages": total_pages,
+            "items": (0..count).map(|index| json!({ "id": format!("{page}-{index}") })).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Answers each page number with its page.
+    #[allow(clippy::future_not_send)]
+    async fn mock_pages(pages: &[(u32, usize, i32)]) -> MockServer {
+        let server = MockServer::start_async().await;
+
+        for &(number, count, total_pages) in pages {
+            server
+                .mock_async(|when, then| {
+                    when.path("/api/collections/articles/records")
+                        .query_param("page", number.to_string())
+                        .query_param("perPage", "2");
+                    then.status(200).json_body(page(number, count, total_pages));
+                })
+                .await;
+        }
+
+        server
+    }
+
+    fn stream(pb: &PocketBase, prefetch: bool) -> RecordStream<'_, Value> {
+        pb.collection("articles")
+            .unwrap()
+            .get_list::<Value>()
+            .per_page(2)
+            .skip_total(true)
+            .prefetch(prefetch)
+            .into_stream()
+    }
+
+    async fn ids(stream: RecordStream<'_, Value>) -> Vec<String> {
+        stream
+            .map(|record| record.unwrap()["id"].as_str().unwrap().to_string())
+            .collect()
             .await
-            .map_err(|error| RequestError::ParseError(error.to_string()))?;
+    }
+
+    #[tokio::test]
+    async fn stops_on_the_last_page() {
+        let server = mock_pages(&[(1, 2, 2), (2, 2, 2), (3, 2, 2)]).await;
+        let pb = PocketBase::new(&server.base_url());
+
+        assert_eq!(ids(stream(&pb, false)).await, ["1-0", "1-1", "2-0", "2-1"]);
+    }
 
-        Ok(records)
+    #[tokio::test]
+    async fn stops_on_an_empty_page() {
+        let server = mock_pages(&[(1, 2, -1), (2, 0, -1), (3, 2, -1)]).await;
+        let pb = PocketBase::new(&server.base_url());
+
+        assert_eq!(ids(stream(&pb, false)).await, ["1-0", "1-1"]);
+    }
+
+    #[tokio::test]
+    async fn stops_on_a_short_page() {
+        let server = mock_pages(&[(1, 2, -1), (2, 1, -1), (3, 2, -1)]).await;
+        let pb = PocketBase::new(&server.base_url());
+
+        assert_eq!(ids(stream(&pb, false)).await, ["1-0", "1-1", "2-0"]);
+    }
+
+    #[tokio::test]
+    async fn prefetches_the_next_page_in_order() {
+        let server = mock_pages(&[(1, 2, -1), (2, 2, -1), (3, 1, -1)]).await;
+        let pb = PocketBase::new(&server.base_url());
+
+        let mut records = stream(&pb, false);
+        records.next().await.unwrap().unwrap();
+        assert!(records.pending.is_none());
+
+        let mut records = stream(&pb, true);
+        records.next().await.unwrap().unwrap();
+        assert!(records.pending.is_some());
+
+        assert_eq!(ids(records).await, ["1-1", "2-0", "2-1", "3-0"]);
+    }
+
+    #[tokio::test]
+    async fn ends_after_an_error() {
+        let server = mock_pages(&[(1, 2, -1)]).await;
+        server
+            .mock_async(|when, then| {
+                when.query_param("page", "2");
+                then.status(500)
+                    .json_body(json!({ "status": 500, "message": "Oops.", "data": {} }));
+            })
+            .await;
+        let pb = PocketBase::new(&server.base_url());
+
+        let results = stream(&pb, true).collect::<Vec<_>>().await;
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok() && results[1].is_ok());
+        assert!(
+            matches!(&results[2], Err(RequestError::Unhandled(error)) if error.message() == "Oops.")
+        );
     }
 }
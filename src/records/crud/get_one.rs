@@ -1,13 +1,17 @@
+use std::borrow::Cow;
+
 use serde::{de::DeserializeOwned, Deserialize};
 
 use crate::error::RequestError;
+use crate::expand::validate_expand_depth;
 use crate::{Collection, PocketBase};
 
 pub struct CollectionGetOneBuilder<'a, T: Send + Deserialize<'a>> {
     client: &'a PocketBase,
-    collection_name: &'a str,
+    collection_name: Cow<'a, str>,
     record_id: &'a str,
-    expand: Option<&'a str>,
+    expand: Option<Cow<'a, str>>,
+    fields: Option<Cow<'a, str>>,
     _marker: std::marker::PhantomData<T>,
 }
 
@@ -34,12 +38,12 @@ impl<'a> Collection<'a> {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn Error>> {
-    ///     let mut pb = PocketBase::new("http://localhost:8090");
+    ///     let pb = PocketBase::new("http://localhost:8090");
     ///
     ///     // ...
     ///
     ///     let article = pb
-    ///         .collection("articles")
+    ///         .collection("articles")?
     ///         .get_one::<Article>("record_id_123")
     ///         .call()
     ///         .await?;
@@ -50,7 +54,7 @@ impl<'a> Collection<'a> {
     /// }
     /// ```
     #[must_use]
-    pub const fn get_one<T: Default + DeserializeOwned + Clone + Send>(
+    pub fn get_one<T: Default + DeserializeOwned + Clone + Send>(
         self,
         record_id: &'a str,
     ) -> CollectionGetOneBuilder<'a, T> {
@@ -59,6 +63,7 @@ impl<'a> Collection<'a> {
             collection_name: self.name,
             record_id,
             expand: None,
+            fields: None,
             _marker: std::marker::PhantomData,
         }
     }
@@ -75,8 +80,32 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetOneBuilder<'
     /// Supports up to 6-levels depth nested relations expansion.
     /// The expanded relations will be appended to each individual record under the `expand` property (eg. `"expand": {"relField1": {...}, ...}`).
     /// Only the relations to which the request user has permissions to **view** will be expanded.
-    pub const fn expand(mut self, expand: &'a str) -> Self {
-        self.expand = Some(expand);
+    ///
+    /// A [`crate::Expand`] builds the relations to expand, and can be given instead of a string.
+    /// A relation nested deeper than 6 levels is rejected with a [`RequestError::BadRequest`],
+    /// without sending the request.
+    pub fn expand(mut self, expand: impl Into<Cow<'a, str>>) -> Self {
+        self.expand = Some(expand.into());
+        self
+    }
+
+    /// Comma separated string of the fields to return in the JSON response *(by default returns all fields)*.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?fields=*,expand.relField.name
+    /// ```
+    ///
+    /// `*` targets all keys from the specific depth level.
+    ///
+    /// The `:excerpt(maxLength, withEllipsis)` modifier returns the first `maxLength` characters
+    /// of a text field as plain text, ending with `...` if `withEllipsis` is `true`
+    /// *(example: `?fields=*,description:excerpt(200,true)`)*.
+    ///
+    /// A [`crate::Fields`] builds the list, and can be given instead of a string.
+    /// [`crate::Fields::of`] lists the fields of `T`, so that only them are downloaded.
+    pub fn fields(mut self, fields: impl Into<Cow<'a, str>>) -> Self {
+        self.fields = Some(fields.into());
         self
     }
 
@@ -85,48 +114,41 @@ impl<'a, T: Default + DeserializeOwned + Clone + Send> CollectionGetOneBuilder<'
     /// This method finalizes the request built using the builder pattern
     /// and sends it to the API endpoint. It should be called after all
     /// desired parameters and configurations have been set on the builder.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "get_one",
+            skip_all,
+            fields(
+                collection = %self.collection_name,
+                record_id = %self.record_id,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
     pub async fn call(self) -> Result<T, RequestError> {
         let url = format!(
             "{}/api/collections/{}/records/{}",
             self.client.base_url, self.collection_name, self.record_id
         );
 
-        let request = self.expand.map_or_else(
-            || self.client.request_get(&url, None),
-            |expand_value| {
-                let expand_params = vec![("expand", expand_value)];
+        let mut query_parameters: Vec<(&str, &str)> = vec![];
 
-                self.client.request_get(&url, Some(expand_params))
-            },
-        );
+        if let Some(expand) = self.expand.as_deref() {
+            validate_expand_depth(expand)?;
+            query_parameters.push(("expand", expand));
+        }
 
-        let request = request.send().await;
+        if let Some(fields) = self.fields.as_deref().filter(|fields| !fields.is_empty()) {
+            query_parameters.push(("fields", fields));
+        }
 
-        let response = match request {
-            Ok(response) => response
-                .error_for_status()
-                .map_err(|err| match err.status() {
-                    Some(reqwest::StatusCode::FORBIDDEN) => RequestError::Forbidden,
-                    Some(reqwest::StatusCode::NOT_FOUND) => RequestError::NotFound,
-                    Some(reqwest::StatusCode::TOO_MANY_REQUESTS) => RequestError::TooManyRequests,
-                    _ => RequestError::Unhandled,
-                })?,
-            Err(error) => {
-                return Err(match error.status() {
-                    Some(reqwest::StatusCode::FORBIDDEN) => RequestError::Forbidden,
-                    Some(reqwest::StatusCode::NOT_FOUND) => RequestError::NotFound,
-                    Some(reqwest::StatusCode::TOO_MANY_REQUESTS) => RequestError::TooManyRequests,
-                    _ => RequestError::Unhandled,
-                });
-            }
-        };
+        let request = self.client.request_get(&url, Some(query_parameters));
 
-        // Parse JSON response
-        let record = response
-            .json::<T>()
+        self.client
+            .send_and_decode::<T>(request)
             .await
-            .map_err(|error| RequestError::ParseError(error.to_string()))?;
-
-        Ok(record)
+            .map_err(RequestError::from)
     }
 }
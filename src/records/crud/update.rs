@@ -1,8 +1,12 @@
-use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Number, Value};
 use thiserror::Error;
 
-use crate::error::{BadRequestError, BadRequestResponse};
-use crate::{Collection, PocketBase};
+use crate::error::{Error, ErrorKind};
+use crate::records::crud::create::{record_query, RecordBody};
+use crate::{Collection, Form, Part, PocketBase};
 
 /// Represents the various errors that can be obtained after a `update` request.
 #[derive(Error, Debug)]
@@ -11,64 +15,95 @@ pub enum UpdateError {
     /// but returned a [400 Bad Request]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/400") HTTP error response.
     ///
     /// One or more fields were not validated `PocketBase`.
-    #[error("One or more fields were not validated : {0:?}")]
-    BadRequest(Vec<BadRequestError>),
+    #[error("One or more fields were not validated : {:?}", .0.data())]
+    BadRequest(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [401 Unauthorized]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/401") HTTP error response.
+    ///
+    /// The request requires a valid authorization token.
+    #[error("The request requires a valid authorization token.")]
+    Unauthorized(Error),
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [403 Forbidden]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/403") HTTP error response.
     ///
     /// The authorized record is not allowed to perform this action.
     #[error("The authorized record is not allowed to perform this action.")]
-    Forbidden,
+    Forbidden(Error),
     /// Communication with the `PocketBase` API was successful,
     /// but returned a [404 Not Found]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/404") HTTP error response.
     ///
     /// The requested resource wasn't found. Missing collection context.
     #[error("The requested resource wasn't found. Missing collection context.")]
-    NotFound,
+    NotFound(Error),
+    /// Communication with the `PocketBase` API was successful,
+    /// but returned a [429 Too Many Requests]("https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/429") HTTP error response.
+    ///
+    /// The server is rate limiting requests. Wait before retrying.
+    #[error("Too many requests. Please wait before retrying.")]
+    TooManyRequests(Error),
     /// Communication with the `PocketBase` API failed.
     ///
     /// This could be caused by an internet outage, an error in the link given to the `PocketBase` SDK
     /// and similar errors.
     #[error("The communication with the PocketBase API failed: {0}")]
-    Unreachable(String),
+    Unreachable(Error),
     /// The response could not be parsed into the expected data structure.
     #[error("Could not parse response into the expected data structure. It usually means that there is a missmatch between the provided Generic Type Parameter and your Collection definition: {0}")]
-    ParseError(String),
+    ParseError(Error),
     /// The response from the `PocketBase` instance API was unexpected.
     /// If you think its an error, please [open an issue on GitHub]("https://github.com/fromhorizons/pocketbase-rs/issues").
     #[error("An unhandled status code was returned by the PocketBase API: {0}")]
-    UnexpectedResponse(String),
+    UnexpectedResponse(Error),
 }
 
-pub struct CollectionUpdateBuilder<'a, T: Send + Serialize + Deserialize<'a>> {
+impl From<Error> for UpdateError {
+    fn from(error: Error) -> Self {
+        match error.kind() {
+            ErrorKind::BadRequest | ErrorKind::InvalidInput => Self::BadRequest(error),
+            ErrorKind::Unauthorized => Self::Unauthorized(error),
+            ErrorKind::Forbidden => Self::Forbidden(error),
+            ErrorKind::NotFound => Self::NotFound(error),
+            ErrorKind::TooManyRequests => Self::TooManyRequests(error),
+            ErrorKind::Unreachable => Self::Unreachable(error),
+            ErrorKind::ParseError => Self::ParseError(error),
+            _ => Self::UnexpectedResponse(error),
+        }
+    }
+}
+
+pub struct CollectionUpdateBuilder<'a, T: Default + Serialize + Clone + Send> {
     client: &'a PocketBase,
-    collection_name: &'a str,
+    collection_name: Cow<'a, str>,
     record_id: &'a str,
-    data: T,
-    _marker: std::marker::PhantomData<T>,
+    body: RecordBody<T>,
+    modifiers: Map<String, Value>,
+    files: Vec<(String, Part)>,
+    expand: Option<Cow<'a, str>>,
+    fields: Option<Cow<'a, str>>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct UpdateResponse {
-    pub collection_name: String,
-    pub collection_id: String,
-    pub id: String,
-    pub updated: String,
-    pub created: String,
+/// The fields of a record, followed by the field modifiers of the update.
+#[derive(Serialize)]
+struct WithModifiers<'r, T> {
+    #[serde(flatten)]
+    record: &'r T,
+    #[serde(flatten)]
+    modifiers: &'r Map<String, Value>,
 }
 
 impl<'a> Collection<'a> {
     /// Update a single record.
     ///
-    /// On success, this function returns a [`UpdateResponse`] struct, otherwise returns a [`UpdateError`], which may include:
+    /// This function returns a `CollectionUpdateBuilder`, which allows you to expand the
+    /// relations or select the fields of the updated record before calling `.call().await` to
+    /// execute the request.
     ///
     /// # Example
     ///
     /// ```rust,ignore
     /// use std::error::Error;
     ///
-    /// use pocketbase_rs::{AuthenticationError, PocketBaseAdminBuilder};
+    /// use pocketbase_rs::{AuthenticationError, PocketBaseAdminBuilder, Record};
     /// use serde::{Deserialize, Serialize};
     ///
     /// #[derive(Default, Serialize, Deserialize, Clone, Debug)]
@@ -89,8 +124,9 @@ impl<'a> Collection<'a> {
     ///     };
     ///
     ///     let request = admin_pb
-    ///         .collection("articles")
+    ///         .collection("articles")?
     ///         .update::<Article>("jla0s0s86d83wx8", updated_article)
+    ///         .call::<Record<Article>>()
     ///         .await;
     ///
     ///     match request {
@@ -101,75 +137,455 @@ impl<'a> Collection<'a> {
     ///     Ok(())
     /// }
     /// ```
+    #[must_use]
+    pub fn update<T: Default + Serialize + Clone + Send>(
+        self,
+        record_id: &'a str,
+        record: T,
+    ) -> CollectionUpdateBuilder<'a, T> {
+        CollectionUpdateBuilder {
+            client: self.client,
+            collection_name: self.name,
+            record_id,
+            body: RecordBody::Json(record),
+            modifiers: Map::new(),
+            files: Vec::new(),
+            expand: None,
+            fields: None,
+        }
+    }
+
+    /// Update a single record, from the given [`crate::Form`].
+    ///
+    /// If you don't need to upload files, you probably want the "simpler" [`Collection::update()`] method.
+    ///
+    /// Only the fields of the form are updated. To add or remove a single file without
+    /// sending the other ones again, see [`CollectionUpdateBuilder::append_file`] and
+    /// [`CollectionUpdateBuilder::remove`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use pocketbase_rs::{Form, Part, Record};
+    ///
+    /// let image = std::fs::read("./vulpes_vulpes.jpg")?;
+    ///
+    /// let image_part = Part::bytes(image)
+    ///     .file_name("vulpes_vulpes.jpg")
+    ///     .mime_str("image/jpeg")?;
+    ///
+    /// let fox = pb
+    ///     .collection("foxes")?
+    ///     .update_multipart("jla0s0s86d83wx8", Form::new().text("name", "Red Fox"))
+    ///     .append_file("pictures", image_part)
+    ///     .remove("pictures", "blurry_fox_f8a4kq3x1z.jpg")
+    ///     .call::<Record<Fox>>()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn update_multipart(
+        self,
+        record_id: &'a str,
+        form: Form,
+    ) -> CollectionUpdateBuilder<'a, ()> {
+        CollectionUpdateBuilder {
+            client: self.client,
+            collection_name: self.name,
+            record_id,
+            body: RecordBody::Multipart(form),
+            modifiers: Map::new(),
+            files: Vec::new(),
+            expand: None,
+            fields: None,
+        }
+    }
+}
+
+impl<'a, T: Default + Serialize + Clone + Send> CollectionUpdateBuilder<'a, T> {
+    /// Appends the given value(s) to a relation, select or file field, with the `field+` modifier.
+    ///
+    /// For a relation or select field, the value is an id or an option, or an array of them.
+    /// To upload a new file, see [`CollectionUpdateBuilder::append_file`].
+    /// For a number field, see [`CollectionUpdateBuilder::increment`].
+    ///
+    /// The record can be `()` to only update the field modifiers, for example
+    /// `.update("record_id", ()).append("tags", ["rust", "pocketbase"])`.
+    pub fn append(mut self, field: &str, value: impl Into<Value>) -> Self {
+        self.push_modifier(format!("{field}+"), value.into());
+        self
+    }
+
+    /// Prepends the given value(s) to a relation, select or file field, with the `+field` modifier.
+    ///
+    /// For a relation or select field, the value is an id or an option, or an array of them.
+    /// To upload a new file, see [`CollectionUpdateBuilder::prepend_file`].
+    pub fn prepend(mut self, field: &str, value: impl Into<Value>) -> Self {
+        self.push_modifier(format!("+{field}"), value.into());
+        self
+    }
+
+    /// Removes the given value(s) from a relation, select or file field, with the `field-` modifier.
+    ///
+    /// For a file field, the values are the names of the files to delete.
+    /// For a number field, see [`CollectionUpdateBuilder::decrement`].
+    pub fn remove(mut self, field: &str, value: impl Into<Value>) -> Self {
+        self.push_modifier(format!("{field}-"), value.into());
+        self
+    }
+
+    /// Adds the given number to the current value of a number field, with the `field+` modifier.
+    ///
+    /// Numbers given several times for the same field are summed.
+    pub fn increment(mut self, field: &str, value: impl Into<Value>) -> Self {
+        self.push_number_modifier(format!("{field}+"), value.into());
+        self
+    }
+
+    /// Subtracts the given number from the current value of a number field, with the `field-`
+    /// modifier.
+    ///
+    /// Numbers given several times for the same field are summed.
+    pub fn decrement(mut self, field: &str, value: impl Into<Value>) -> Self {
+        self.push_number_modifier(format!("{field}-"), value.into());
+        self
+    }
+
+    /// Uploads a new file after the existing files of a file field, with the `field+` modifier.
+    ///
+    /// The other files are kept, and don't need to be sent again.
+    pub fn append_file(mut self, field: &str, file: Part) -> Self {
+        self.files.push((format!("{field}+"), file));
+        self
+    }
+
+    /// Uploads a new file before the existing files of a file field, with the `+field` modifier.
+    ///
+    /// The other files are kept, and don't need to be sent again.
+    pub fn prepend_file(mut self, field: &str, file: Part) -> Self {
+        self.files.push((format!("+{field}"), file));
+        self
+    }
+
+    /// Auto expand the relations of the updated record.
+    ///
+    /// Example:
+    /// ```toml
+    /// ?expand=relField1,relField2.subRelField
+    /// ```
+    ///
+    /// A [`crate::Expand`] builds the relations to expand, and can be given instead of a string.
+    /// A relation nested deeper than 6 levels is rejected with an [`UpdateError::BadRequest`],
+    /// without sending the request.
+    pub fn expand(mut self, expand: impl Into<Cow<'a, str>>) -> Self {
+        self.expand = Some(expand.into());
+        self
+    }
+
+    /// Comma separated string of the fields of the updated record to return in the JSON response
+    /// *(by default returns all fields)*.
+    ///
+    /// A [`crate::Fields`] builds the list, and can be given instead of a string.
+    pub fn fields(mut self, fields: impl Into<Cow<'a, str>>) -> Self {
+        self.fields = Some(fields.into());
+        self
+    }
+
+    /// Sends the request and returns the updated record.
+    ///
+    /// `PocketBase` returns the whole record, with its computed fields and autodates.
+    /// It is deserialized into `R`, for example the struct of the record, or a
+    /// [`crate::Record`] to also read its system fields *(`Record<()>` for only them)*.
     ///
     /// # Errors
     ///
     /// This function will return an error if:
     /// - The request to the server fails (`UpdateError::Unreachable`).
-    /// - The server responds with a bad request status (`UpdateError::BadRequest`).
+    /// - The server responds with a bad request status, or the expanded relations are nested too
+    ///   deeply (`UpdateError::BadRequest`).
+    /// - The request requires a valid authorization token (`UpdateError::Unauthorized`).
     /// - The server responds with a forbidden status (`UpdateError::Forbidden`).
     /// - The record is not found (`UpdateError::NotFound`).
+    /// - The request was rate limited (`UpdateError::TooManyRequests`).
     /// - The server responds with an unexpected status (`UpdateError::UnexpectedResponse`).
     /// - The response could not be parsed into the expected data structure (`UpdateError::ParseError`).
-    pub async fn update<T: Default + Serialize + Clone + Send>(
-        self,
-        record_id: &'a str,
-        record: T,
-    ) -> Result<UpdateResponse, UpdateError> {
-        let collection_name = self.name;
-
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "update",
+            skip_all,
+            fields(
+                collection = %self.collection_name,
+                record_id = %self.record_id,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn call<R: DeserializeOwned>(self) -> Result<R, UpdateError> {
         let endpoint = format!(
             "{}/api/collections/{}/records/{}",
-            self.client.base_url, collection_name, record_id
+            self.client.base_url, self.collection_name, self.record_id
         );
 
-        let request = self
-            .client
-            .request_patch_json(&endpoint, &record)
-            .send()
-            .await;
+        let request = match self.body {
+            RecordBody::Json(record) if self.modifiers.is_empty() && self.files.is_empty() => {
+                self.client.request_patch_json(&endpoint, &record)
+            }
+            RecordBody::Json(record) => {
+                let record = WithModifiers {
+                    record: &record,
+                    modifiers: &self.modifiers,
+                };
 
-        match request {
-            Ok(response) => match response.status() {
-                reqwest::StatusCode::OK => {
-                    let data = response.json::<UpdateResponse>().await;
+                if self.files.is_empty() {
+                    self.client.request_patch_json(&endpoint, &record)
+                } else {
+                    // The other fields of a multipart request are sent as JSON in `@jsonPayload`.
+                    let payload = serde_json::to_string(&record)
+                        .map_err(|error| Error::new(ErrorKind::ParseError, error.to_string()))?;
 
-                    match data {
-                        Ok(data) => Ok(data),
-                        Err(error) => Err(UpdateError::ParseError(error.to_string())),
-                    }
+                    let form = append_files(Form::new().text("@jsonPayload", payload), self.files);
+
+                    self.client.request_patch_form(&endpoint, form)
                 }
+            }
+            RecordBody::Multipart(form) => {
+                let form = append_modifiers(form, self.modifiers);
+                let form = append_files(form, self.files);
+
+                self.client.request_patch_form(&endpoint, form)
+            }
+        };
+
+        let query_parameters = record_query(self.expand.as_deref(), self.fields.as_deref())?;
+
+        let record = self
+            .client
+            .send_and_decode::<R>(request.query(&query_parameters))
+            .await?;
+
+        Ok(record)
+    }
 
-                reqwest::StatusCode::BAD_REQUEST => {
-                    let data = response.json::<BadRequestResponse>().await;
-
-                    match data {
-                        Ok(bad_response) => {
-                            let mut errors: Vec<BadRequestError> = vec![];
-
-                            for (error_name, error_data) in bad_response.data {
-                                errors.push(BadRequestError {
-                                    name: error_name,
-                                    code: error_data.code,
-                                    message: error_data.message,
-                                });
-                            }
-
-                            Err(UpdateError::BadRequest(errors))
-                        }
-                        Err(error) => Err(UpdateError::ParseError(error.to_string())),
-                    }
+    /// Adds a number field modifier, summing it with the number already given for the same key,
+    /// as `PocketBase` only accepts one number per modifier of a number field.
+    fn push_number_modifier(&mut self, key: String, value: Value) {
+        if let Some(Value::Number(current)) = self.modifiers.get_mut(&key) {
+            if let Value::Number(value) = &value {
+                if let Some(sum) = add_numbers(current, value) {
+                    *current = sum;
+                    return;
                 }
+            }
+        }
+
+        self.push_modifier(key, value);
+    }
+
+    /// Adds a field modifier, gathering its values in an array with the ones already given for
+    /// the same key.
+    fn push_modifier(&mut self, key: String, value: Value) {
+        let Some(current) = self.modifiers.get_mut(&key) else {
+            self.modifiers.insert(key, value);
+            return;
+        };
+
+        let mut values = match current.take() {
+            Value::Array(values) => values,
+            current => vec![current],
+        };
+
+        match value {
+            Value::Array(new_values) => values.extend(new_values),
+            value => values.push(value),
+        }
+
+        *current = Value::Array(values);
+    }
+}
+
+/// Adds two numbers, keeping integers as integers while they don't overflow.
+fn add_numbers(a: &Number, b: &Number) -> Option<Number> {
+    if let Some(sum) = a
+        .as_i64()
+        .zip(b.as_i64())
+        .and_then(|(a, b)| a.checked_add(b))
+    {
+        return Some(sum.into());
+    }
+
+    Number::from_f64(a.as_f64()? + b.as_f64()?)
+}
+
+/// Adds the field modifiers to the form, one text part per value.
+fn append_modifiers(form: Form, modifiers: Map<String, Value>) -> Form {
+    modifiers.into_iter().fold(form, |form, (key, value)| {
+        let values = match value {
+            Value::Array(values) => values,
+            value => vec![value],
+        };
 
-                reqwest::StatusCode::FORBIDDEN => Err(UpdateError::Forbidden),
-                reqwest::StatusCode::NOT_FOUND => Err(UpdateError::NotFound),
+        values.into_iter().fold(form, |form, value| match value {
+            Value::String(text) => form.text(key.clone(), text),
+            value => form.text(key.clone(), value.to_string()),
+        })
+    })
+}
+
+/// Adds the uploaded files to the form.
+fn append_files(form: Form, files: Vec<(String, Part)>) -> Form {
+    files
+        .into_iter()
+        .fold(form, |form, (key, file)| form.part(key, file))
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::{Method::PATCH, MockServer};
+    use serde_json::json;
 
-                _ => Err(UpdateError::UnexpectedResponse(
-                    response.status().to_string(),
-                )),
-            },
+    use super::*;
 
-            Err(error) => Err(UpdateError::Unreachable(error.to_string())),
+    #[derive(Default, Serialize, Clone)]
+    struct Article {
+        title: String,
+    }
+
+    fn article() -> Article {
+        Article {
+            title: String::from("Foxes"),
         }
     }
+
+    #[tokio::test]
+    async fn sends_modifiers_in_the_json_body() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(PATCH)
+                    .path("/api/collections/articles/records/r1")
+                    .json_body(json!({
+                        "title": "Foxes",
+                        "views+": 5,
+                        "+tags": ["a", "b", "c"],
+                        "pictures-": "fox.jpg",
+                    }));
+                then.status(200).json_body(json!({}));
+            })
+            .await;
+
+        let pb = PocketBase::new(&server.base_url());
+
+        pb.collection("articles")
+            .unwrap()
+            .update("r1", article())
+            .increment("views", 2)
+            .increment("views", 3)
+            .prepend("tags", "a")
+            .prepend("tags", ["b", "c"])
+            .remove("pictures", "fox.jpg")
+            .call::<Value>()
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn sends_modifiers_in_the_json_payload_with_files() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(PATCH)
+                    .path("/api/collections/articles/records/r1")
+                    .body_contains(
+                        "name=\"@jsonPayload\"\r\n\r\n\
+                         {\"title\":\"Foxes\",\"+tags\":\"a\",\"views-\":1.5}",
+                    )
+                    .body_contains("name=\"pictures+\"; filename=\"fox.jpg\"");
+                then.status(200).json_body(json!({}));
+            })
+            .await;
+
+        let pb = PocketBase::new(&server.base_url());
+
+        pb.collection("articles")
+            .unwrap()
+            .update("r1", article())
+            .decrement("views", 1)
+            .decrement("views", 0.5)
+            .prepend("tags", "a")
+            .append_file("pictures", Part::bytes(vec![0]).file_name("fox.jpg"))
+            .call::<Value>()
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn sends_modifiers_as_multipart_parts() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(PATCH)
+                    .path("/api/collections/articles/records/r1")
+                    .body_contains("name=\"title\"\r\n\r\nFoxes\r\n")
+                    .body_contains("name=\"views+\"\r\n\r\n5\r\n")
+                    .body_contains("name=\"+tags\"\r\n\r\na\r\n")
+                    .body_contains("name=\"+tags\"\r\n\r\nb\r\n")
+                    .body_contains("name=\"pictures-\"\r\n\r\nfox.jpg\r\n");
+                then.status(200).json_body(json!({}));
+            })
+            .await;
+
+        let pb = PocketBase::new(&server.base_url());
+
+        pb.collection("articles")
+            .unwrap()
+            .update_multipart("r1", Form::new().text("title", "Foxes"))
+            .increment("views", 2)
+            .increment("views", 3)
+            .prepend("tags", ["a", "b"])
+            .remove("pictures", "fox.jpg")
+            .call::<Value>()
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn gathers_numeric_relation_and_select_values() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(PATCH)
+                    .path("/api/collections/articles/records/r1")
+                    .json_body(json!({
+                        "ratings+": [4, 5],
+                        "+levels": [1, 2, 3],
+                        "ratings-": [1, 2],
+                    }));
+                then.status(200).json_body(json!({}));
+            })
+            .await;
+
+        let pb = PocketBase::new(&server.base_url());
+
+        pb.collection("articles")
+            .unwrap()
+            .update("r1", ())
+            .append("ratings", 4)
+            .append("ratings", 5)
+            .prepend("levels", 1)
+            .prepend("levels", [2, 3])
+            .remove("ratings", 1)
+            .remove("ratings", 2)
+            .call::<Value>()
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+    }
 }
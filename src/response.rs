@@ -0,0 +1,160 @@
+use std::time::Instant;
+
+use reqwest::{RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+
+use crate::error::{Error, ErrorKind};
+use crate::PocketBase;
+
+impl PocketBase {
+    /// Sends a request with [`PocketBase::send`], and decodes its JSON response into `T`.
+    ///
+    /// Every endpoint goes through this pipeline, so that they all handle responses the same way:
+    /// - A failed communication gives an [`ErrorKind::Unreachable`] error.
+    /// - An unsuccessful status gives the [`ErrorKind`] matching the status, with the `message` and
+    ///   the field errors of the JSON error body, or the [`ErrorKind::MfaRequired`] challenge.
+    /// - A successful response is decoded into `T`. An empty body *(`204 No Content`)* is decoded
+    ///   as `null`, so that endpoints without response can use [`serde::de::IgnoredAny`].
+    ///
+    /// The time spent until the failure is recorded in the error, see [`Error::elapsed`].
+    pub(crate) async fn send_and_decode<T: DeserializeOwned>(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> Result<T, Error> {
+        let started_at = Instant::now();
+        let response = self.send(request_builder).await;
+
+        decode_response(response, started_at).await
+    }
+
+    /// Same as [`PocketBase::send_and_decode`], without the automatic token refresh.
+    ///
+    /// Used by authentication requests, as a `401` is part of their flow.
+    pub(crate) async fn send_and_decode_without_refresh<T: DeserializeOwned>(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> Result<T, Error> {
+        let started_at = Instant::now();
+        let response = self.send_without_refresh(request_builder).await;
+
+        decode_response(response, started_at).await
+    }
+}
+
+/// Decodes the response of a request sent at `started_at`, see [`PocketBase::send_and_decode`].
+///
+/// Also used by the realtime connection, which has its own HTTP client.
+pub async fn decode_response<T: DeserializeOwned>(
+    response: reqwest::Result<Response>,
+    started_at: Instant,
+) -> Result<T, Error> {
+    let decoded = async {
+        let response = response?;
+
+        if !response.status().is_success() {
+            return Err(Error::from_response(response).await);
+        }
+
+        let body = response.bytes().await?;
+        let body: &[u8] = if body.is_empty() { b"null" } else { &body };
+
+        serde_json::from_slice(body)
+            .map_err(|error| Error::new(ErrorKind::ParseError, error.to_string()))
+    }
+    .await;
+
+    decoded.map_err(|error| error.with_elapsed(started_at.elapsed()))
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    use super::*;
+
+    /// Sends a `GET` to a mock answering with `status` and `body`, and decodes the response.
+    #[allow(clippy::future_not_send)]
+    async fn decode_status(status: u16, body: &str) -> Result<serde_json::Value, Error> {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(GET).path("/api/test");
+                then.status(status)
+                    .header("Content-Type", "application/json")
+                    .body(body);
+            })
+            .await;
+
+        let pb = PocketBase::new(&server.base_url());
+
+        pb.send_and_decode(pb.request_get(&server.url("/api/test"), None))
+            .await
+    }
+
+    #[tokio::test]
+    async fn maps_each_status_to_its_kind() {
+        let body = json!({ "status": 0, "message": "Refused.", "data": {} }).to_string();
+
+        for (status, kind) in [
+            (400, ErrorKind::BadRequest),
+            (401, ErrorKind::Unauthorized),
+            (403, ErrorKind::Forbidden),
+            (404, ErrorKind::NotFound),
+            (429, ErrorKind::TooManyRequests),
+            (500, ErrorKind::ServerError),
+            (503, ErrorKind::ServerError),
+            (409, ErrorKind::UnexpectedResponse),
+        ] {
+            let error = decode_status(status, &body).await.unwrap_err();
+
+            assert_eq!(error.kind(), kind, "status {status}");
+            assert_eq!(error.status().map(|status| status.as_u16()), Some(status));
+            assert_eq!(error.message(), "Refused.");
+        }
+    }
+
+    #[tokio::test]
+    async fn describes_non_json_error_bodies() {
+        let error = decode_status(502, "<html>Bad Gateway</html>")
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::ServerError);
+        assert_eq!(error.message(), "Bad Gateway");
+        assert!(error.data().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reports_undecodable_success_bodies() {
+        let error = decode_status(200, "{ not json").await.unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::ParseError);
+        assert_eq!(error.status(), None);
+    }
+
+    #[tokio::test]
+    async fn decodes_success_and_empty_bodies() {
+        let value = decode_status(200, r#"{ "id": "r1" }"#).await.unwrap();
+        assert_eq!(value, json!({ "id": "r1" }));
+
+        let value = decode_status(204, "").await.unwrap();
+        assert_eq!(value, serde_json::Value::Null);
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_servers() {
+        // Nothing listens on the discard port.
+        let pb = PocketBase::new("http://127.0.0.1:9");
+
+        let error = pb
+            .send_and_decode::<serde_json::Value>(
+                pb.request_get("http://127.0.0.1:9/api/test", None),
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::Unreachable);
+        assert!(error.elapsed().is_some());
+    }
+}
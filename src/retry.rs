@@ -0,0 +1,314 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Method, Request, Response, StatusCode};
+
+use crate::PocketBase;
+
+/// Describes how a [`PocketBase`] client retries the requests that failed for a transient reason.
+///
+/// Only idempotent requests *(`GET` and `DELETE`)* are retried, when:
+/// - The `PocketBase` instance couldn't be reached, or didn't answer in time.
+/// - The response is a `429 Too Many Requests`, `502 Bad Gateway`, `503 Service Unavailable`
+///   or `504 Gateway Timeout`.
+///
+/// The delay between two attempts doubles after each attempt, from `initial_backoff` up to
+/// `max_backoff`, and is randomized with jitter so that many clients don't retry at once.
+/// When the response has a `Retry-After` header, its delay is used instead. If it is longer
+/// than `max_backoff`, the response is returned without retrying.
+///
+/// Set it with [`crate::PocketBaseBuilder::retry_policy`] or [`PocketBase::set_retry_policy`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::time::Duration;
+///
+/// use pocketbase_rs::{PocketBase, RetryPolicy};
+///
+/// let pb = PocketBase::builder("http://localhost:8090")
+///     .retry_policy(
+///         RetryPolicy::new(5)
+///             .initial_backoff(Duration::from_secs(1))
+///             .max_backoff(Duration::from_secs(30)),
+///     )
+///     .build()?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, with a backoff starting at 500 milliseconds, of at most 30 seconds, with jitter.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy sending each request at most `max_attempts` times, first attempt included.
+    ///
+    /// The other settings have their default values, see [`RetryPolicy::default`].
+    #[must_use]
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the delay before the first retry *(default to 500 milliseconds)*.
+    #[must_use]
+    pub const fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum delay between two attempts *(default to 30 seconds)*.
+    #[must_use]
+    pub const fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Enables or disables the randomization of the delays *(enabled by default)*.
+    ///
+    /// With jitter, each delay is picked between half and the whole of the exponential backoff.
+    #[must_use]
+    pub const fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns the delay to wait after the given failed attempt, starting at 1.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff));
+
+        if self.jitter {
+            let half = backoff / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            backoff
+        }
+    }
+}
+
+impl PocketBase {
+    /// Sets the retry policy of the client, or disables retries with `None`.
+    ///
+    /// This setting applies to this client, and to the clones made afterwards.
+    pub const fn set_retry_policy(&mut self, retry_policy: Option<RetryPolicy>) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Executes the request, retrying it according to the retry policy of the client.
+    pub(crate) async fn execute(
+        &self,
+        client: &reqwest::Client,
+        mut request: Request,
+    ) -> reqwest::Result<Response> {
+        let Some(policy) = self.retry_policy else {
+            return client.execute(request).await;
+        };
+
+        if !matches!(*request.method(), Method::GET | Method::DELETE) {
+            return client.execute(request).await;
+        }
+
+        let mut attempt = 1;
+
+        loop {
+            let next_request = if attempt < policy.max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            let result = client.execute(request).await;
+
+            let Some(next_request) = next_request else {
+                return result;
+            };
+
+            let delay = match &result {
+                Ok(response) if is_transient(response.status()) => match retry_after(response) {
+                    Some(delay) if delay > policy.max_backoff => return result,
+                    Some(delay) => delay,
+                    None => policy.backoff(attempt),
+                },
+                Err(error) if error.is_connect() || error.is_timeout() => policy.backoff(attempt),
+                _ => return result,
+            };
+
+            tokio::time::sleep(delay).await;
+
+            request = next_request;
+            attempt += 1;
+        }
+    }
+}
+
+/// Whether the status is worth retrying the request for.
+const fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Reads the `Retry-After` header of the response.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+
+    parse_retry_after(value, Utc::now())
+}
+
+/// Parses a `Retry-After` value, either a number of seconds or an HTTP date, into the delay to
+/// wait from `now`.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+
+    // A date in the past gives an error, which is a zero duration.
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::new(3)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(1))
+    }
+
+    #[test]
+    fn doubles_the_backoff_up_to_the_maximum() {
+        let policy = policy().jitter(false);
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn keeps_the_jitter_between_half_and_the_whole_backoff() {
+        let policy = policy();
+
+        for attempt in 1..=6 {
+            let backoff = policy.jitter(false).backoff(attempt);
+
+            for _ in 0..100 {
+                let delay = policy.backoff(attempt);
+
+                assert!(
+                    delay >= backoff / 2 && delay <= backoff,
+                    "{delay:?} for {backoff:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        let now = Utc::now();
+
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn parses_retry_after_dates() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:30:00 GMT", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+    }
+
+    /// Sends a request to `/api/health` with the retry policy of [`policy`].
+    async fn get_health(server: &MockServer) -> Response {
+        let mut pb = PocketBase::new(&server.base_url());
+        pb.set_retry_policy(Some(policy()));
+
+        let request = pb.reqwest_client.get(server.url("/api/health"));
+        let (client, request) = request.build_split();
+
+        pb.execute(&client, request.unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn retries_after_the_given_delay() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/api/health");
+                then.status(503).header("Retry-After", "0");
+            })
+            .await;
+
+        let response = get_health(&server).await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(mock.hits_async().await, 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_when_retry_after_exceeds_the_max_backoff() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(GET).path("/api/health");
+                then.status(503).header("Retry-After", "60");
+            })
+            .await;
+
+        let response = get_health(&server).await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(mock.hits_async().await, 1);
+    }
+}
@@ -0,0 +1,129 @@
+use std::borrow::Cow;
+use std::fmt;
+
+/// A records order, rendered to the `PocketBase` sort syntax *(example: `-created,id`)*.
+///
+/// A `Sort` can be given to the `sort` method of the [`crate::Collection::get_list`],
+/// [`crate::Collection::get_first_list_item`] and [`crate::Collection::get_full_list`] builders.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use pocketbase_rs::Sort;
+///
+/// let sort = Sort::desc("created").then_asc("id");
+///
+/// assert_eq!(sort.to_string(), "-created,id");
+///
+/// let articles = pb
+///     .collection("articles")?
+///     .get_list::<Article>()
+///     .sort(sort)
+///     .call()
+///     .await?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct Sort(Vec<SortKey>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SortKey {
+    Asc(String),
+    Desc(String),
+    Random,
+}
+
+impl Sort {
+    /// The special key sorting the records by their insertion order, for example with
+    /// `Sort::desc(Sort::ROW_ID)`.
+    pub const ROW_ID: &'static str = "@rowid";
+
+    /// Sorts by the given field, in ascending order.
+    pub fn asc(field: impl Into<String>) -> Self {
+        Self(vec![SortKey::Asc(field.into())])
+    }
+
+    /// Sorts by the given field, in descending order.
+    pub fn desc(field: impl Into<String>) -> Self {
+        Self(vec![SortKey::Desc(field.into())])
+    }
+
+    /// Sorts the records randomly (`@random`).
+    pub fn random() -> Self {
+        Self(vec![SortKey::Random])
+    }
+
+    /// Then sorts the records with the same values by the given field, in ascending order.
+    pub fn then_asc(mut self, field: impl Into<String>) -> Self {
+        self.0.push(SortKey::Asc(field.into()));
+        self
+    }
+
+    /// Then sorts the records with the same values by the given field, in descending order.
+    pub fn then_desc(mut self, field: impl Into<String>) -> Self {
+        self.0.push(SortKey::Desc(field.into()));
+        self
+    }
+
+    /// Then sorts the records with the same values randomly (`@random`).
+    pub fn then_random(mut self) -> Self {
+        self.0.push(SortKey::Random);
+        self
+    }
+}
+
+impl fmt::Display for Sort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, key) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+
+            match key {
+                SortKey::Asc(field) => f.write_str(field)?,
+                SortKey::Desc(field) => write!(f, "-{field}")?,
+                SortKey::Random => f.write_str("@random")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Sort> for Cow<'_, str> {
+    fn from(sort: Sort) -> Self {
+        Cow::Owned(sort.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_keys_in_order() {
+        assert_eq!(
+            Sort::desc("created").then_asc("id").to_string(),
+            "-created,id"
+        );
+        assert_eq!(
+            Sort::asc("title").then_desc("views").to_string(),
+            "title,-views"
+        );
+    }
+
+    #[test]
+    fn renders_random() {
+        assert_eq!(Sort::random().to_string(), "@random");
+        assert_eq!(
+            Sort::asc("title").then_random().to_string(),
+            "title,@random"
+        );
+    }
+
+    #[test]
+    fn renders_row_id() {
+        assert_eq!(Sort::desc(Sort::ROW_ID).to_string(), "-@rowid");
+        assert_eq!(Sort::asc(Sort::ROW_ID).to_string(), "@rowid");
+    }
+}
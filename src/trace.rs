@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use reqwest::{Method, Response};
+
+/// Records the status and the latency of a response on the span of the current API call,
+/// and emits a `DEBUG` event for the request.
+///
+/// Only the path of the URL is logged, never the headers nor the query: they may hold
+/// the authorization token or user data.
+pub fn record_response(method: &Method, response: &reqwest::Result<Response>, elapsed: Duration) {
+    let span = tracing::Span::current();
+    let latency_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+
+    span.record("latency_ms", latency_ms);
+
+    match response {
+        Ok(response) => {
+            let status = response.status().as_u16();
+
+            span.record("status", status);
+
+            tracing::debug!(
+                %method,
+                path = response.url().path(),
+                status,
+                latency_ms,
+                "PocketBase responded"
+            );
+        }
+        Err(error) => {
+            tracing::debug!(
+                %method,
+                path = error.url().map(reqwest::Url::path),
+                timeout = error.is_timeout(),
+                latency_ms,
+                "PocketBase couldn't be reached"
+            );
+        }
+    }
+}